@@ -2,6 +2,7 @@ use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
 
 use pinocchio_lambda_vm::{
     circuits::qap::QuadraticArithmeticProgram as Qap,
+    config::DefaultCurve,
     math::elliptic_curve::EllipticCurveElement,
     pinocchio::{
         prover,
@@ -12,7 +13,7 @@ use pinocchio_lambda_vm::{
 
 use crate::{
     fq5::Fq, pinocchio_io_and_witness_from_arkworks_cs, pinocchio_r1cs_from_arkworks_cs,
-    test_utils::PinocchioPaperExampleCircuit,
+    test_utils::PinocchioPaperExampleCircuit, FE,
 };
 
 #[test]
@@ -32,11 +33,11 @@ fn create_proof_from_arkworks_and_verify_it() {
     let r1cs = pinocchio_r1cs_from_arkworks_cs(&cs);
     let (io, witness) = pinocchio_io_and_witness_from_arkworks_cs(&cs);
 
-    let qap: Qap = r1cs.into();
+    let qap: Qap<FE> = r1cs.into();
 
     let (ek, vk): (
-        EvaluationKey<EllipticCurveElement>,
-        VerificationKey<EllipticCurveElement>,
+        EvaluationKey<EllipticCurveElement<DefaultCurve>>,
+        VerificationKey<EllipticCurveElement<DefaultCurve>>,
     ) = setup(&qap, &toxic_waste);
 
     let mut c_vector = io.clone();
@@ -15,7 +15,7 @@ use pinocchio_lambda_vm::math::field_element::FieldElement;
 type FE = FieldElement<ORDER_R>;
 
 /// Generates an `R1CS` compatible with Lambda Pinocchio from an Arkworks `ConstraintSystemRef`
-pub fn pinocchio_r1cs_from_arkworks_cs<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> R1CS {
+pub fn pinocchio_r1cs_from_arkworks_cs<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> R1CS<FE> {
     cs.inline_all_lcs();
 
     let r1cs_matrices = cs.to_matrices().unwrap();
@@ -188,7 +188,7 @@ mod tests {
             Ok(())
         }
     }
-    
+
     // Tests start here
 
     #[test]
@@ -271,8 +271,8 @@ mod tests {
 
         assert_eq!(
             converted_r1cs.number_of_inputs + converted_r1cs.number_of_outputs,
-            test_utils::new_test_r1cs().number_of_inputs
-                + test_utils::new_test_r1cs().number_of_outputs
+            test_utils::new_test_r1cs::<FE>().number_of_inputs
+                + test_utils::new_test_r1cs::<FE>().number_of_outputs
         );
     }
 
@@ -312,8 +312,8 @@ mod tests {
     /// This function changes variable 5 for 6
     /// our current implementation of the paper r1cs and the arkworks
     /// version utilizes a different index, but it is the same r1cs
-    fn swap_last_variables_test_circuit(r1cs: &R1CS) -> R1CS {
-        let mut updated_constraints: Vec<Constraint> = Vec::new();
+    fn swap_last_variables_test_circuit(r1cs: &R1CS<FE>) -> R1CS<FE> {
+        let mut updated_constraints: Vec<Constraint<FE>> = Vec::new();
         for constraint in &r1cs.constraints {
             let mut updated_constraint = constraint.clone();
             updated_constraint.a.swap(5, 6);
@@ -0,0 +1,105 @@
+//! Searches for small pairing-friendly curves and prints their parameters.
+//!
+//! Usage:
+//!   cargo run --release --bin curve_search -- [options]
+//!
+//! Options:
+//!   --family supersingular|ordinary   (default: supersingular)
+//!   --embedding-degree <k>            (default: 2)
+//!   --subgroup-bits <min>..=<max>     (default: 3..=8; as in Rust,
+//!                   or <min>..<max>    the second form leaves <max> out)
+//!   --max-field-bits <bits>           (default: 12)
+//!   --max-cofactor <h>                (default: 16)
+//!   --counting naive|bsgs             (default: bsgs)
+//!   --count <n>                       (default: 5)
+use pinocchio_lambda_vm::math::curves::search::{
+    search, CurveFamily, PointCounting, SearchParameters,
+};
+use std::process;
+
+fn parse_arguments(arguments: &[String]) -> Result<SearchParameters, String> {
+    let mut parameters = SearchParameters {
+        family: CurveFamily::Supersingular,
+        embedding_degree: 2,
+        subgroup_bits: 3..=8,
+        max_field_bits: 12,
+        max_cofactor: 16,
+        point_counting: PointCounting::BabyStepGiantStep,
+        max_results: 5,
+    };
+    let number = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| format!("Expected a number, got {value}"))
+    };
+    let small_number = |value: &str| {
+        u32::try_from(number(value)?).map_err(|_| format!("{value} does not fit in 32 bits"))
+    };
+    for pair in arguments.chunks(2) {
+        let [option, value] = pair else {
+            return Err(format!("Missing value for {}", pair[0]));
+        };
+        match option.as_str() {
+            "--family" => {
+                parameters.family = match value.as_str() {
+                    "supersingular" => CurveFamily::Supersingular,
+                    "ordinary" => CurveFamily::Ordinary,
+                    _ => return Err(format!("Unknown family {value}")),
+                }
+            }
+            "--embedding-degree" => parameters.embedding_degree = small_number(value)?,
+            "--subgroup-bits" => {
+                let (min, max) = value
+                    .split_once("..")
+                    .ok_or(format!("Expected a range like 3..=8, got {value}"))?;
+                let empty = || format!("The range {value} is empty");
+                let min = small_number(min)?;
+                let max = match max.strip_prefix('=') {
+                    Some(max) => small_number(max)?,
+                    // As in Rust, a range without `=` leaves its end out.
+                    None => small_number(max)?.checked_sub(1).ok_or_else(empty)?,
+                };
+                if min > max {
+                    return Err(empty());
+                }
+                parameters.subgroup_bits = min..=max;
+            }
+            "--max-field-bits" => parameters.max_field_bits = small_number(value)?,
+            "--max-cofactor" => parameters.max_cofactor = number(value)?,
+            "--counting" => {
+                parameters.point_counting = match value.as_str() {
+                    "naive" => PointCounting::Naive,
+                    "bsgs" => PointCounting::BabyStepGiantStep,
+                    _ => return Err(format!("Unknown point counting method {value}")),
+                }
+            }
+            "--count" => {
+                parameters.max_results =
+                    usize::try_from(number(value)?).map_err(|_| format!("{value} is too large"))?
+            }
+            _ => return Err(format!("Unknown option {option}")),
+        }
+    }
+    Ok(parameters)
+}
+
+fn main() {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let parameters = parse_arguments(&arguments).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    });
+    let curves = search(&parameters).unwrap_or_else(|error| {
+        eprintln!("Invalid search parameters: {error:?}");
+        process::exit(1);
+    });
+    if curves.is_empty() {
+        eprintln!("No curves found.");
+    }
+    for curve in curves {
+        println!("// {curve}");
+        if let Some(code) = curve.to_curve_params() {
+            println!("{code}");
+        }
+    }
+}
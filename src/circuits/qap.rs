@@ -1,18 +1,14 @@
-use super::super::config::ORDER_R;
 use super::r1cs::R1CS;
-use crate::math::{field_element::FieldElement, polynomial::Polynomial as Poly};
+use crate::math::{field::Field, polynomial::Polynomial};
 use std::convert::From;
 
-type FE = FieldElement<ORDER_R>;
-type Polynomial = Poly<ORDER_R>;
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// QAP Representation of the circuits
-pub struct QuadraticArithmeticProgram {
-    pub vs: Vec<Polynomial>,
-    pub ws: Vec<Polynomial>,
-    pub ys: Vec<Polynomial>,
-    pub target: Polynomial,
+pub struct QuadraticArithmeticProgram<F: Field> {
+    pub vs: Vec<Polynomial<F>>,
+    pub ws: Vec<Polynomial<F>>,
+    pub ys: Vec<Polynomial<F>>,
+    pub target: Polynomial<F>,
     pub number_of_inputs: usize,
     pub number_of_outputs: usize,
 }
@@ -22,7 +18,7 @@ pub enum CreationError {
     PolynomialVectorsSizeMismatch,
 }
 
-impl QuadraticArithmeticProgram {
+impl<F: Field> QuadraticArithmeticProgram<F> {
     /// Creates a new QAP
     /// This expects vectors to be organized like:
     /// v0,w0,y0
@@ -30,10 +26,10 @@ impl QuadraticArithmeticProgram {
     /// mid associated polynomials
     /// outputs associated v,w,y polynomials
     pub fn new(
-        vs: Vec<Polynomial>,
-        ws: Vec<Polynomial>,
-        ys: Vec<Polynomial>,
-        target: Polynomial,
+        vs: Vec<Polynomial<F>>,
+        ws: Vec<Polynomial<F>>,
+        ys: Vec<Polynomial<F>>,
+        target: Polynomial<F>,
         number_of_inputs: usize,
         number_of_outputs: usize,
     ) -> Result<Self, CreationError> {
@@ -52,113 +48,113 @@ impl QuadraticArithmeticProgram {
         }
     }
 
-    pub fn h_polynomial(&self, c: &[FE]) -> Polynomial {
+    pub fn h_polynomial(&self, c: &[F]) -> Polynomial<F> {
         self.p_polynomial(c).div_with_ref(&self.target)
     }
     /// Receives C elements of a solution of the circuit
     /// Returns p polynomial
     // This along the polynomial execution should be migrated with a better
     // representation of the circuit
-    pub fn p_polynomial(&self, cs: &[FE]) -> Polynomial {
-        let v: Polynomial = self.vs[0].clone()
+    pub fn p_polynomial(&self, cs: &[F]) -> Polynomial<F> {
+        let v: Polynomial<F> = self.vs[0].clone()
             + self.vs[1..]
                 .iter()
                 .zip(cs)
-                .map(|(v, c)| v.mul_with_ref(&Polynomial::new_monomial(*c, 0)))
+                .map(|(v, c)| v.mul_with_ref(&Polynomial::new_monomial(c.clone(), 0)))
                 .reduce(|x, y| x + y)
                 .unwrap();
 
-        let w: Polynomial = self.ws[0].clone()
+        let w: Polynomial<F> = self.ws[0].clone()
             + self.ws[1..]
                 .iter()
                 .zip(cs)
-                .map(|(w, c)| w.mul_with_ref(&Polynomial::new_monomial(*c, 0)))
+                .map(|(w, c)| w.mul_with_ref(&Polynomial::new_monomial(c.clone(), 0)))
                 .reduce(|x, y| x + y)
                 .unwrap();
 
-        let y: Polynomial = self.ys[0].clone()
+        let y: Polynomial<F> = self.ys[0].clone()
             + self.ys[1..]
                 .iter()
                 .zip(cs)
-                .map(|(y, c)| y.mul_with_ref(&Polynomial::new_monomial(*c, 0)))
+                .map(|(y, c)| y.mul_with_ref(&Polynomial::new_monomial(c.clone(), 0)))
                 .reduce(|x, y| x + y)
                 .unwrap();
 
         v * w - y
     }
 
-    pub fn v_mid(&'_ self) -> &[Polynomial] {
+    pub fn v_mid(&'_ self) -> &[Polynomial<F>] {
         &self.vs[self.number_of_inputs + 1..(self.vs.len() - self.number_of_outputs)]
     }
 
-    pub fn w_mid(&'_ self) -> &[Polynomial] {
+    pub fn w_mid(&'_ self) -> &[Polynomial<F>] {
         &self.ws[self.number_of_inputs + 1..(self.ws.len() - self.number_of_outputs)]
     }
 
-    pub fn y_mid(&'_ self) -> &[Polynomial] {
+    pub fn y_mid(&'_ self) -> &[Polynomial<F>] {
         &self.ys[self.number_of_inputs + 1..(self.ys.len() - self.number_of_outputs)]
     }
 
-    pub fn v_input(&'_ self) -> &[Polynomial] {
+    pub fn v_input(&'_ self) -> &[Polynomial<F>] {
         &self.vs[1..self.number_of_inputs + 1]
     }
 
-    pub fn w_input(&'_ self) -> &[Polynomial] {
+    pub fn w_input(&'_ self) -> &[Polynomial<F>] {
         &self.ws[1..self.number_of_inputs + 1]
     }
 
-    pub fn y_input(&'_ self) -> &[Polynomial] {
+    pub fn y_input(&'_ self) -> &[Polynomial<F>] {
         &self.ys[1..self.number_of_inputs + 1]
     }
 
-    pub fn v0(&'_ self) -> &Polynomial {
+    pub fn v0(&'_ self) -> &Polynomial<F> {
         &self.vs[0]
     }
 
-    pub fn w0(&'_ self) -> &Polynomial {
+    pub fn w0(&'_ self) -> &Polynomial<F> {
         &self.ws[0]
     }
 
-    pub fn y0(&'_ self) -> &Polynomial {
+    pub fn y0(&'_ self) -> &Polynomial<F> {
         &self.ys[0]
     }
 
-    pub fn v_output(&'_ self) -> &[Polynomial] {
+    pub fn v_output(&'_ self) -> &[Polynomial<F>] {
         &self.vs[(self.vs.len() - self.number_of_outputs)..]
     }
-    pub fn w_output(&'_ self) -> &[Polynomial] {
+    pub fn w_output(&'_ self) -> &[Polynomial<F>] {
         &self.ws[(self.ws.len() - self.number_of_outputs)..]
     }
 
-    pub fn y_output(&'_ self) -> &[Polynomial] {
+    pub fn y_output(&'_ self) -> &[Polynomial<F>] {
         &self.ys[(self.ys.len() - self.number_of_outputs)..]
     }
 }
 
-impl From<R1CS> for QuadraticArithmeticProgram {
+impl<F: Field> From<R1CS<F>> for QuadraticArithmeticProgram<F> {
     /// Transforms a R1CS to a QAP
-    fn from(r1cs: R1CS) -> Self {
+    fn from(r1cs: R1CS<F>) -> Self {
         // The r values for the qap polynomial can each be any number,
         // as long as there are the right amount of rs
         // In this case, it's set them to be 0,1,2..number_of_constraints(),
         // number_of_constraints non inclusive
-        let rs: Vec<FE> = (0..r1cs.number_of_constraints() as u128)
-            .map(FE::new)
+        let rs: Vec<F> = (0..r1cs.number_of_constraints() as u64)
+            .map(F::from_u64)
             .collect();
 
-        let mut vs: Vec<Polynomial> = Vec::with_capacity(r1cs.witness_size());
-        let mut ws: Vec<Polynomial> = Vec::with_capacity(r1cs.witness_size());
-        let mut ys: Vec<Polynomial> = Vec::with_capacity(r1cs.witness_size());
-        let mut t: Polynomial = Polynomial::new_monomial(FE::new(1), 0);
+        let mut vs: Vec<Polynomial<F>> = Vec::with_capacity(r1cs.witness_size());
+        let mut ws: Vec<Polynomial<F>> = Vec::with_capacity(r1cs.witness_size());
+        let mut ys: Vec<Polynomial<F>> = Vec::with_capacity(r1cs.witness_size());
+        let mut t: Polynomial<F> = Polynomial::new_monomial(F::one(), 0);
 
         for r in &rs {
-            t = t * Polynomial::new(vec![-*r, FE::new(1)]);
+            t = t * Polynomial::new(vec![-r.clone(), F::one()]);
         }
 
         for i in 0..r1cs.witness_size() {
-            let v_ys: Vec<FE> = r1cs.constraints.iter().map(|c| c.a[i]).collect();
-            let w_ys: Vec<FE> = r1cs.constraints.iter().map(|c| c.b[i]).collect();
-            let y_ys: Vec<FE> = r1cs.constraints.iter().map(|c| c.c[i]).collect();
+            let v_ys: Vec<F> = r1cs.constraints.iter().map(|c| c.a[i].clone()).collect();
+            let w_ys: Vec<F> = r1cs.constraints.iter().map(|c| c.b[i].clone()).collect();
+            let y_ys: Vec<F> = r1cs.constraints.iter().map(|c| c.c[i].clone()).collect();
 
             vs.push(Polynomial::interpolate(&rs, &v_ys));
             ws.push(Polynomial::interpolate(&rs, &w_ys));
@@ -183,6 +179,10 @@ mod tests {
     };
 
     use super::*;
+    use crate::config::ORDER_R;
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<ORDER_R>;
 
     #[test]
     fn qap_with_different_amount_of_polynomials_should_error() {
@@ -202,7 +202,7 @@ mod tests {
 
     #[test]
     fn test_circuit_v_w_y_have_7_elements() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.vs.len(), 7);
         assert_eq!(test_circuit.ws.len(), 7);
         assert_eq!(test_circuit.ys.len(), 7);
@@ -211,77 +211,77 @@ mod tests {
     //_mid polynomials of test circuit contains only one polynomial
     #[test]
     fn v_mid_test_circuit_on_r6_is_0() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         let r6 = test_qap_r6();
         assert_eq!(test_circuit.y_mid()[0].evaluate(r6), FE::new(0));
     }
 
     #[test]
     fn w_mid_test_circuit_has_one_element() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.v_mid().len(), 1);
     }
 
     #[test]
     fn w_mid_test_circuit_on_r5_is_0() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         let r5 = test_qap_r5();
         assert_eq!(test_circuit.w_mid()[0].evaluate(r5), FE::new(0));
     }
 
     #[test]
     fn w_mid_test_circuit_on_r6_is_1() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         let r6 = test_qap_r6();
         assert_eq!(test_circuit.w_mid()[0].evaluate(r6), FE::new(1));
     }
 
     #[test]
     fn y_mid_test_circuit_on_r5_is_1() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         let r5 = test_qap_r5();
         assert_eq!(test_circuit.y_mid()[0].evaluate(r5), FE::new(1));
     }
 
     #[test]
     fn y_mid_test_circuit_on_r6_is_0() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         let r6 = test_qap_r6();
         assert_eq!(test_circuit.y_mid()[0].evaluate(r6), FE::new(0));
     }
 
     #[test]
     fn v_input_test_circuit_has_length_4() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.v_input().len(), 4);
     }
 
     #[test]
     fn w_input_test_circuit_has_length_4() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.w_input().len(), 4);
     }
     #[test]
     fn y_input_test_circuit_has_length_4() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.y_input().len(), 4);
     }
 
     #[test]
     fn v_output_test_circuit_has_length_1() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.v_output().len(), 1);
     }
 
     #[test]
     fn w_output_test_circuit_has_length_1() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.w_output().len(), 1);
     }
 
     #[test]
     fn y_output_test_circuit_has_length_1() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
         assert_eq!(test_circuit.y_output().len(), 1);
     }
 
@@ -289,7 +289,7 @@ mod tests {
     /// This test runs multiple cases calculated in paper
     /// t polynomial is tested implicitly by calculating h = p / t
     fn test_polynomial_h_cases() {
-        let test_circuit = new_test_qap();
+        let test_circuit = new_test_qap::<FE>();
 
         let inputs = [FE::new(1), FE::new(2), FE::new(3), FE::new(4)];
 
@@ -361,7 +361,7 @@ mod tests {
     fn test_r1cs_into_qap_is_test_qap() {
         let qap = new_test_qap();
         let r1cs = new_test_r1cs();
-        let r1cs_as_qap: QuadraticArithmeticProgram = r1cs.into();
+        let r1cs_as_qap: QuadraticArithmeticProgram<FE> = r1cs.into();
         assert_eq!(qap, r1cs_as_qap);
     }
 }
@@ -1,7 +1,4 @@
-use super::super::config::ORDER_R;
-use crate::math::field_element::FieldElement;
-
-type FE = FieldElement<ORDER_R>;
+use crate::math::field::Field;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum CreationError {
@@ -13,10 +10,10 @@ pub enum CreationError {
 
 /// R1CS representation of an Arithmetic Program
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Constraint {
-    pub a: Vec<FE>,
-    pub b: Vec<FE>,
-    pub c: Vec<FE>,
+pub struct Constraint<F: Field> {
+    pub a: Vec<F>,
+    pub b: Vec<F>,
+    pub c: Vec<F>,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// R1CS represented as a vector of constraints/gates
@@ -24,8 +21,8 @@ pub struct Constraint {
 /// in the first Matrix of the R1CS
 /// joining the second vectors results on the second matrix, and so on
 ///
-pub struct R1CS {
-    pub constraints: Vec<Constraint>,
+pub struct R1CS<F: Field> {
+    pub constraints: Vec<Constraint<F>>,
     // These are not strictly part of a R1CS
     // But they are data of the constraint system
     // that is needed to generate the proof
@@ -33,10 +30,10 @@ pub struct R1CS {
     pub number_of_outputs: usize,
 }
 
-impl R1CS {
+impl<F: Field> R1CS<F> {
     #[allow(dead_code)]
     pub fn new(
-        constraints: Vec<Constraint>,
+        constraints: Vec<Constraint<F>>,
         number_of_inputs: usize,
         number_of_outputs: usize,
     ) -> Result<Self, CreationError> {
@@ -62,13 +59,13 @@ impl R1CS {
     }
 
     pub fn new_with_matrixes(
-        a: Vec<Vec<FE>>,
-        b: Vec<Vec<FE>>,
-        c: Vec<Vec<FE>>,
+        a: Vec<Vec<F>>,
+        b: Vec<Vec<F>>,
+        c: Vec<Vec<F>>,
         num_inputs: usize,
         num_outputs: usize,
     ) -> Result<Self, CreationError> {
-        let mut constraints: Vec<Constraint> = Vec::with_capacity(a.len());
+        let mut constraints: Vec<Constraint<F>> = Vec::with_capacity(a.len());
         // TO DO:
         // - Check if sizes match
         // - Remove clones
@@ -79,7 +76,7 @@ impl R1CS {
     }
 
     #[allow(dead_code)]
-    pub fn verify_solution(self, s: &[FE]) -> bool {
+    pub fn verify_solution(self, s: &[F]) -> bool {
         for constraint in self.constraints {
             if !constraint.verify_solution(s) {
                 return false;
@@ -102,10 +99,10 @@ impl R1CS {
     }
 }
 
-impl Constraint {
+impl<F: Field> Constraint<F> {
     /// Creates a new constraint for a,b,c vectors
     #[allow(dead_code)]
-    pub fn new(a: Vec<FE>, b: Vec<FE>, c: Vec<FE>) -> Result<Self, CreationError> {
+    pub fn new(a: Vec<F>, b: Vec<F>, c: Vec<F>) -> Result<Self, CreationError> {
         if a.len() != b.len() || a.len() != c.len() || b.len() != c.len() {
             Err(CreationError::VectorsSizeMismatch)
         } else {
@@ -114,20 +111,18 @@ impl Constraint {
     }
 
     #[allow(dead_code)]
-    pub fn verify_solution(self, s: &[FE]) -> bool {
+    pub fn verify_solution(self, s: &[F]) -> bool {
         inner_product(&self.a, s) * inner_product(&self.b, s) == inner_product(&self.c, s)
     }
 }
 
-pub fn inner_product(v1: &[FE], v2: &[FE]) -> FE {
+pub fn inner_product<F: Field>(v1: &[F], v2: &[F]) -> F {
     v1.iter()
         .zip(v2)
-        .map(|(x, y)| *x * *y)
-        .fold(FE::new(0), |x, y| x + y)
+        .map(|(x, y)| x.clone() * y.clone())
+        .fold(F::zero(), |x, y| x + y)
 }
 
-// v1.iter().zip(v2).map(|(x, y)| *x * *y).fold(FE::new(0), |x,y| x + y)
-
 #[cfg(test)]
 pub mod tests {
     use crate::circuits::test_utils::{
@@ -135,6 +130,10 @@ pub mod tests {
     };
 
     use super::*;
+    use crate::config::ORDER_R;
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<ORDER_R>;
 
     #[test]
     fn mul_vectors_2_2_3_3_equals_12() {
@@ -1,143 +1,141 @@
-use super::super::config::ORDER_R;
 use super::{
     qap::QuadraticArithmeticProgram as QAP,
     r1cs::{Constraint, R1CS},
 };
-use crate::math::{field_element::FieldElement, polynomial::Polynomial};
-
-type FE = FieldElement<ORDER_R>;
+use crate::math::{field::Field, polynomial::Polynomial};
 
 // r5 and r6 are exposed to help testing
-pub fn test_qap_r5() -> FE {
-    FE::new(0)
+pub fn test_qap_r5<F: Field>() -> F {
+    F::from_u64(0)
 }
 
-pub fn test_qap_r6() -> FE {
-    FE::new(1)
+pub fn test_qap_r6<F: Field>() -> F {
+    F::from_u64(1)
 }
 
 /// This is a solver for the test qap
 /// Inputs: c1,c2,c3,c4 circuit inputs
 /// Outputs: c5 intermediate result, c6 result
-pub fn test_qap_solver(inputs: [FE; 4]) -> (FE, FE) {
-    let c5 = inputs[2] * inputs[3];
-    let c6 = (inputs[0] + inputs[1]) * c5;
+pub fn test_qap_solver<F: Field>(inputs: [F; 4]) -> (F, F) {
+    let [c1, c2, c3, c4] = inputs;
+    let c5 = c3 * c4;
+    let c6 = (c1 + c2) * c5.clone();
     (c5, c6)
 }
 
 /// Test qap based on pinocchios paper example
-pub fn new_test_qap() -> QAP {
-    let r5: FE = test_qap_r5();
-    let r6: FE = test_qap_r6();
+pub fn new_test_qap<F: Field>() -> QAP<F> {
+    let r5: F = test_qap_r5();
+    let r6: F = test_qap_r6();
 
-    let t: Polynomial<ORDER_R> =
-        Polynomial::new(vec![-r5, FE::new(1)]) * Polynomial::new(vec![-r6, FE::new(1)]);
+    let t: Polynomial<F> = Polynomial::new(vec![-r5.clone(), F::from_u64(1)])
+        * Polynomial::new(vec![-r6.clone(), F::from_u64(1)]);
 
     let vs = &[
         // v0 is 0 for everypoint for the circuit, since it has no constants
         // in the paper they don't write it
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
         // v1..v6 are the ones explicitly written in the paper
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(1)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(1)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(1), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(1)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(1)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(1), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
     ];
 
     let ws = &[
         //w0
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
         //w1
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(1), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(1)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(1), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(1)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
     ];
 
     let ys = &[
         //y0
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
         //y1
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(1), FE::new(0)]),
-        Polynomial::interpolate(&[r5, r6], &[FE::new(0), FE::new(1)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(1), F::from_u64(0)]),
+        Polynomial::interpolate(&[r5.clone(), r6.clone()], &[F::from_u64(0), F::from_u64(1)]),
     ];
 
     QAP::new(vs.to_vec(), ws.to_vec(), ys.to_vec(), t, 4, 1).unwrap()
 }
 
-pub fn new_test_r1cs() -> R1CS {
+pub fn new_test_r1cs<F: Field>() -> R1CS<F> {
     let constraints = vec![new_test_first_constraint(), new_test_second_constraint()];
     R1CS::new(constraints, 4, 1).unwrap()
 }
 
-pub fn new_test_first_constraint() -> Constraint {
+pub fn new_test_first_constraint<F: Field>() -> Constraint<F> {
     Constraint {
         a: vec![
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(1),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(1),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
         ],
         b: vec![
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(1),
-            FE::new(0),
-            FE::new(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(1),
+            F::from_u64(0),
+            F::from_u64(0),
         ],
         c: vec![
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(1),
-            FE::new(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(1),
+            F::from_u64(0),
         ],
     }
 }
 
-pub fn new_test_second_constraint() -> Constraint {
+pub fn new_test_second_constraint<F: Field>() -> Constraint<F> {
     Constraint {
         a: vec![
-            FE::new(0),
-            FE::new(1),
-            FE::new(1),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
+            F::from_u64(0),
+            F::from_u64(1),
+            F::from_u64(1),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
         ],
         b: vec![
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(1),
-            FE::new(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(1),
+            F::from_u64(0),
         ],
         c: vec![
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(0),
-            FE::new(1),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(0),
+            F::from_u64(1),
         ],
     }
 }
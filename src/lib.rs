@@ -1,4 +1,5 @@
 pub mod circuits;
 pub mod config;
 pub mod math;
+pub mod parallel;
 pub mod pinocchio;
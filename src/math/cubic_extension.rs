@@ -0,0 +1,267 @@
+use super::field::{ConditionalSwap, Field, FrobeniusField};
+use super::field_element::FieldElementError;
+use std::fmt::Debug;
+use std::ops;
+
+/// Parameters of the cubic extension F[v] / (v^3 - non_residue)
+/// of a base field F.
+pub trait CubicExtensionParams: Debug + Clone + PartialEq + Eq {
+    type BaseField: Field;
+
+    /// Returns the cubic non-residue whose cube root `v` is adjoined
+    /// to the base field.
+    fn non_residue() -> Self::BaseField;
+
+    /// Returns `v^(p - 1) = non_residue^((p - 1) / 3)`, where p is the characteristic,
+    /// so that the Frobenius map sends `v` to `frobenius_coefficient() * v`.
+    fn frobenius_coefficient() -> Self::BaseField;
+}
+
+/// Represents the element `c0 + c1 * v + c2 * v^2` of a cubic extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubicExtensionElement<P: CubicExtensionParams> {
+    c0: P::BaseField,
+    c1: P::BaseField,
+    c2: P::BaseField,
+}
+
+impl<P: CubicExtensionParams> CubicExtensionElement<P> {
+    /// Creates the element `c0 + c1 * v + c2 * v^2`.
+    pub fn new(c0: P::BaseField, c1: P::BaseField, c2: P::BaseField) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    /// Creates an element belonging to the base field.
+    pub fn new_base(c0: P::BaseField) -> Self {
+        Self::new(c0, P::BaseField::zero(), P::BaseField::zero())
+    }
+
+    /// Returns the coefficient of `v^0`.
+    pub fn c0(&self) -> &P::BaseField {
+        &self.c0
+    }
+
+    /// Returns the coefficient of `v^1`.
+    pub fn c1(&self) -> &P::BaseField {
+        &self.c1
+    }
+
+    /// Returns the coefficient of `v^2`.
+    pub fn c2(&self) -> &P::BaseField {
+        &self.c2
+    }
+
+    /// Multiplies every coefficient by an element of the base field.
+    pub fn mul_by_base(&self, factor: &P::BaseField) -> Self {
+        Self::new(
+            self.c0.clone() * factor.clone(),
+            self.c1.clone() * factor.clone(),
+            self.c2.clone() * factor.clone(),
+        )
+    }
+}
+
+impl<P: CubicExtensionParams> ops::Add for CubicExtensionElement<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.c0 + other.c0, self.c1 + other.c1, self.c2 + other.c2)
+    }
+}
+
+impl<P: CubicExtensionParams> ops::Neg for CubicExtensionElement<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.c0, -self.c1, -self.c2)
+    }
+}
+
+impl<P: CubicExtensionParams> ops::Sub for CubicExtensionElement<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.c0 - other.c0, self.c1 - other.c1, self.c2 - other.c2)
+    }
+}
+
+impl<P: CubicExtensionParams> ops::Mul for CubicExtensionElement<P> {
+    type Output = Self;
+
+    /// Karatsuba multiplication: six multiplications in the base field
+    /// plus two by the non-residue.
+    /// See "Multiplication and Squaring on Pairing-Friendly Fields", section 4.
+    fn mul(self, other: Self) -> Self {
+        let v0 = self.c0.clone() * other.c0.clone();
+        let v1 = self.c1.clone() * other.c1.clone();
+        let v2 = self.c2.clone() * other.c2.clone();
+        let c0 = v0.clone()
+            + P::non_residue()
+                * ((self.c1.clone() + self.c2.clone()) * (other.c1.clone() + other.c2.clone())
+                    - v1.clone()
+                    - v2.clone());
+        let c1 =
+            (self.c0.clone() + self.c1) * (other.c0.clone() + other.c1) - v0.clone() - v1.clone()
+                + P::non_residue() * v2.clone();
+        let c2 = (self.c0 + self.c2) * (other.c0 + other.c2) - v0 - v2 + v1;
+        Self::new(c0, c1, c2)
+    }
+}
+
+impl<P: CubicExtensionParams> ops::Div for CubicExtensionElement<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, dividend: Self) -> Self {
+        self * Field::inv(&dividend).unwrap()
+    }
+}
+
+impl<P: CubicExtensionParams> Field for CubicExtensionElement<P> {
+    const EXTENSION_DEGREE: usize = 3 * P::BaseField::EXTENSION_DEGREE;
+
+    fn zero() -> Self {
+        Self::new_base(P::BaseField::zero())
+    }
+
+    fn one() -> Self {
+        Self::new_base(P::BaseField::one())
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::new_base(P::BaseField::from_u64(value))
+    }
+
+    /// Computes the inverse through the adjugate of the multiplication matrix,
+    /// so that only one inversion in the base field is needed.
+    fn inv(&self) -> Result<Self, FieldElementError> {
+        let (c0, c1, c2) = (self.c0.clone(), self.c1.clone(), self.c2.clone());
+        let t0 = c0.clone() * c0.clone() - P::non_residue() * c1.clone() * c2.clone();
+        let t1 = P::non_residue() * c2.clone() * c2.clone() - c0.clone() * c1.clone();
+        let t2 = c1.clone() * c1.clone() - c0.clone() * c2.clone();
+        let norm = c0 * t0.clone() + P::non_residue() * (c2 * t1.clone() + c1 * t2.clone());
+        let norm_inverse = norm.inv()?;
+        Ok(Self::new(t0, t1, t2).mul_by_base(&norm_inverse))
+    }
+
+    fn random() -> Self {
+        Self::new(
+            P::BaseField::random(),
+            P::BaseField::random(),
+            P::BaseField::random(),
+        )
+    }
+
+    /// Concatenates the encodings of the coefficients, highest degree first.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.c2.to_bytes();
+        bytes.extend(self.c1.to_bytes());
+        bytes.extend(self.c0.to_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError> {
+        let length = P::BaseField::byte_length();
+        if bytes.len() != 3 * length {
+            return Err(FieldElementError::InvalidLength);
+        }
+        let c2 = P::BaseField::from_bytes(&bytes[..length])?;
+        let c1 = P::BaseField::from_bytes(&bytes[length..2 * length])?;
+        let c0 = P::BaseField::from_bytes(&bytes[2 * length..])?;
+        Ok(Self::new(c0, c1, c2))
+    }
+}
+
+impl<P: CubicExtensionParams> ConditionalSwap for CubicExtensionElement<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        P::BaseField::conditional_swap(&mut a.c0, &mut b.c0, mask);
+        P::BaseField::conditional_swap(&mut a.c1, &mut b.c1, mask);
+        P::BaseField::conditional_swap(&mut a.c2, &mut b.c2, mask);
+    }
+}
+
+impl<P: CubicExtensionParams> FrobeniusField for CubicExtensionElement<P>
+where
+    P::BaseField: FrobeniusField,
+{
+    /// (c0 + c1 * v + c2 * v^2)^p = c0^p + c1^p * v^(p - 1) * v + c2^p * v^(2 * (p - 1)) * v^2
+    fn frobenius(&self) -> Self {
+        let coefficient = P::frobenius_coefficient();
+        Self::new(
+            self.c0.frobenius(),
+            self.c1.frobenius() * coefficient.clone(),
+            self.c2.frobenius() * coefficient.clone() * coefficient,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::field_element::FieldElement;
+
+    // 2 is not a cube modulo 7, since the cubes are 0, 1 and 6.
+    type FE = FieldElement<7>;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Two;
+    impl CubicExtensionParams for Two {
+        type BaseField = FE;
+
+        fn non_residue() -> FE {
+            FE::new(2)
+        }
+
+        // 2^((7 - 1) / 3)
+        fn frobenius_coefficient() -> FE {
+            FE::new(4)
+        }
+    }
+    #[allow(clippy::upper_case_acronyms)]
+    type FEE = CubicExtensionElement<Two>;
+
+    #[test]
+    fn v_cubed_is_the_non_residue() {
+        let v = FEE::new(FE::new(0), FE::new(1), FE::new(0));
+        assert_eq!(v.clone() * v.clone() * v, FEE::new_base(FE::new(2)));
+    }
+
+    #[test]
+    fn mul_matches_schoolbook_multiplication() {
+        // (1 + 2v + 3v^2)(4 + 5v + 6v^2)
+        // = 4 + 13v + 28v^2 + 27v^3 + 18v^4
+        // = (4 + 54) + (13 + 36)v + 28v^2
+        let a = FEE::new(FE::new(1), FE::new(2), FE::new(3));
+        let b = FEE::new(FE::new(4), FE::new(5), FE::new(6));
+        assert_eq!(a * b, FEE::new(FE::new(2), FE::new(0), FE::new(0)));
+    }
+
+    #[test]
+    fn every_non_zero_element_times_its_inverse_is_one() {
+        for c0 in 0..7 {
+            for c1 in 0..7 {
+                let a = FEE::new(FE::new(c0), FE::new(c1), FE::new(3));
+                assert_eq!(a.clone() * a.inv().unwrap(), FEE::one());
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_of_zero_is_an_error() {
+        assert_eq!(FEE::zero().inv(), Err(FieldElementError::DivisionByZero));
+    }
+
+    #[test]
+    fn frobenius_is_the_p_power() {
+        for c0 in 0..7 {
+            for c2 in 0..7 {
+                let a = FEE::new(FE::new(c0), FE::new(5), FE::new(c2));
+                assert_eq!(a.frobenius(), a.pow(7u64));
+                assert_eq!(a.frobenius_power(3), a);
+            }
+        }
+    }
+}
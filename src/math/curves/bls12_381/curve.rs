@@ -0,0 +1,70 @@
+use super::field_extension::{Fp, Fp2, Fr};
+use crate::math::field::Field;
+use crate::math::short_weierstrass::{ShortWeierstrassParams, ShortWeierstrassPoint};
+use crate::math::unsigned_integer::UnsignedInteger;
+
+/// BLS12-381 curve y^2 = x^3 + 4 over Fp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BLS12381G1Params;
+impl ShortWeierstrassParams for BLS12381G1Params {
+    type BaseField = Fp;
+    type ScalarField = Fr;
+    type Cofactor = u128;
+
+    /// (x - 1)^2 / 3
+    const COFACTOR: u128 = 0x396c8c005555e1568c00aaab0000aaab;
+
+    fn a() -> Fp {
+        Fp::zero()
+    }
+
+    fn b() -> Fp {
+        Fp::from_u64(4)
+    }
+
+    fn generator_affine() -> (Fp, Fp) {
+        (
+            Fp::from_hex("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"),
+            Fp::from_hex("08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"),
+        )
+    }
+}
+
+/// Sextic twist y^2 = x^3 + 4 * (1 + u) over Fp2.
+/// Its points of order r map to G2 through (x, y) -> (x / w^2, y / w^3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BLS12381G2Params;
+impl ShortWeierstrassParams for BLS12381G2Params {
+    type BaseField = Fp2;
+    type ScalarField = Fr;
+    type Cofactor = UnsignedInteger<8>;
+
+    /// (x^8 - 4x^7 + 5x^6 - 4x^4 + 6x^3 - 4x^2 - 4x + 13) / 9
+    const COFACTOR: UnsignedInteger<8> = UnsignedInteger::from_hex(
+        "0x5d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddfa628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5",
+    );
+
+    fn a() -> Fp2 {
+        Fp2::zero()
+    }
+
+    fn b() -> Fp2 {
+        Fp2::new(Fp::from_u64(4), Fp::from_u64(4))
+    }
+
+    fn generator_affine() -> (Fp2, Fp2) {
+        (
+            Fp2::new(
+                Fp::from_hex("024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"),
+                Fp::from_hex("13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"),
+            ),
+            Fp2::new(
+                Fp::from_hex("0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"),
+                Fp::from_hex("0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"),
+            ),
+        )
+    }
+}
+
+pub type G1Point = ShortWeierstrassPoint<BLS12381G1Params>;
+pub type G2Point = ShortWeierstrassPoint<BLS12381G2Params>;
@@ -0,0 +1,120 @@
+use crate::math::cubic_extension::{CubicExtensionElement, CubicExtensionParams};
+use crate::math::field::Field;
+use crate::math::montgomery_field_element::{IsModulus, MontgomeryFieldElement};
+use crate::math::quadratic_extension::{QuadraticExtensionElement, QuadraticExtensionParams};
+use crate::math::unsigned_integer::{U256, U384};
+
+/// Order of the base field of BLS12-381.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BLS12381FieldModulus;
+impl IsModulus<6> for BLS12381FieldModulus {
+    const MODULUS: U384 = U384::from_hex("0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+}
+
+/// Order of the subgroups G1 and G2 of BLS12-381.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BLS12381ScalarModulus;
+impl IsModulus<4> for BLS12381ScalarModulus {
+    const MODULUS: U256 =
+        U256::from_hex("0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+}
+
+/// Base field of BLS12-381.
+pub type Fp = MontgomeryFieldElement<BLS12381FieldModulus, 6>;
+/// Scalar field of BLS12-381.
+pub type Fr = MontgomeryFieldElement<BLS12381ScalarModulus, 4>;
+
+/// Fp2 = Fp[u] / (u^2 + 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree2ExtensionParams;
+impl QuadraticExtensionParams for Degree2ExtensionParams {
+    type BaseField = Fp;
+
+    fn non_residue() -> Fp {
+        -Fp::one()
+    }
+
+    /// (-1)^((p - 1) / 2) = -1, since p = 3 mod 4.
+    fn frobenius_coefficient() -> Fp {
+        -Fp::one()
+    }
+}
+pub type Fp2 = QuadraticExtensionElement<Degree2ExtensionParams>;
+
+/// Fp6 = Fp2[v] / (v^3 - (1 + u))
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree6ExtensionParams;
+impl CubicExtensionParams for Degree6ExtensionParams {
+    type BaseField = Fp2;
+
+    fn non_residue() -> Fp2 {
+        Fp2::new(Fp::one(), Fp::one())
+    }
+
+    /// xi^((p - 1) / 3)
+    fn frobenius_coefficient() -> Fp2 {
+        Fp2::new(
+            Fp::zero(),
+            Fp::from_hex("0x1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac"),
+        )
+    }
+}
+pub type Fp6 = CubicExtensionElement<Degree6ExtensionParams>;
+
+/// Fp12 = Fp6[w] / (w^2 - v)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree12ExtensionParams;
+impl QuadraticExtensionParams for Degree12ExtensionParams {
+    type BaseField = Fp6;
+
+    fn non_residue() -> Fp6 {
+        Fp6::new(Fp2::zero(), Fp2::one(), Fp2::zero())
+    }
+
+    /// v^((p - 1) / 2) = xi^((p - 1) / 6), since p = 1 mod 6.
+    fn frobenius_coefficient() -> Fp6 {
+        Fp6::new_base(Fp2::new(
+            Fp::from_hex("0x1904d3bf02bb0667c231beb4202c0d1f0fd603fd3cbd5f4f7b2443d784bab9c4f67ea53d63e7813d8d0775ed92235fb8"),
+            Fp::from_hex("0xfc3e2b36c4e03288e9e902231f9fb854a14787b6c7b36fec0c8ec971f63c5f282d5ac14d6c7ec22cf78a126ddc4af3"),
+        ))
+    }
+}
+pub type Fp12 = QuadraticExtensionElement<Degree12ExtensionParams>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::field::FrobeniusField;
+
+    #[test]
+    fn w_to_the_sixth_is_the_cubic_non_residue() {
+        let w = Fp12::new(Fp6::zero(), Fp6::one());
+        let xi = Degree6ExtensionParams::non_residue();
+        assert_eq!(w.pow(6u64), Fp12::new_base(Fp6::new_base(xi)));
+    }
+
+    #[test]
+    fn fp12_inverse_works() {
+        let a = Fp12::random();
+        assert_eq!(a.clone() * a.inv().unwrap(), Fp12::one());
+    }
+
+    #[test]
+    fn frobenius_is_the_p_power_in_every_level_of_the_tower() {
+        let p = BLS12381FieldModulus::MODULUS;
+        let a = Fp2::random();
+        assert_eq!(a.frobenius(), a.pow(p));
+        let b = Fp6::random();
+        assert_eq!(b.frobenius(), b.pow(p));
+        let c = Fp12::random();
+        assert_eq!(c.frobenius(), c.pow(p));
+        assert_eq!(c.frobenius_power(12), c);
+    }
+
+    #[test]
+    fn fp12_has_degree_12_and_its_conjugate_is_the_p_to_the_sixth_power() {
+        assert_eq!(Fp12::EXTENSION_DEGREE, 12);
+        let c = Fp12::random();
+        assert_eq!(c.frobenius_power(6), c.conjugate());
+    }
+}
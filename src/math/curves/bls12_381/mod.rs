@@ -0,0 +1,207 @@
+//! BLS12-381, the pairing-friendly curve with roughly 128 bits of security
+//! described in the IETF draft "Pairing-Friendly Curves".
+pub mod curve;
+pub mod field_extension;
+pub mod pairing;
+
+use self::curve::{G1Point, G2Point};
+use self::field_extension::{Fp12, Fr};
+use crate::math::cyclic_group::PairingEngine;
+
+/// The optimal ate pairing of BLS12-381, mapping G1 x G2 to the
+/// subgroup of order r of Fp12.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BLS12381;
+
+impl PairingEngine for BLS12381 {
+    type Scalar = Fr;
+    type G1 = G1Point;
+    type G2 = G2Point;
+    type GT = Fp12;
+    type G1Prepared = pairing::G1Prepared;
+    type G2Prepared = pairing::G2Prepared;
+
+    fn pairing(p: &G1Point, q: &G2Point) -> Fp12 {
+        pairing::pairing(p, q)
+    }
+
+    fn multi_pairing(pairs: &[(G1Point, G2Point)]) -> Fp12 {
+        pairing::multi_pairing(pairs)
+    }
+
+    fn prepare_g1(p: &G1Point) -> pairing::G1Prepared {
+        pairing::G1Prepared::new(p)
+    }
+
+    fn prepare_g2(q: &G2Point) -> pairing::G2Prepared {
+        pairing::G2Prepared::new(q)
+    }
+
+    fn multi_pairing_prepared(pairs: &[(&pairing::G1Prepared, &pairing::G2Prepared)]) -> Fp12 {
+        pairing::multi_pairing_prepared(pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::field_extension::BLS12381ScalarModulus;
+    use super::*;
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::elliptic_curve::EllipticCurveError;
+    use crate::math::field::Field;
+    use crate::math::montgomery_field_element::IsModulus;
+
+    // Test vectors from the IETF draft "Pairing-Friendly Curves", appendix B.1,
+    // with points encoded in the ZCash format. The 381 bit field leaves the three
+    // most significant bits free, so the flags of `to_bytes_compressed` go there.
+    const G1_COMPRESSED: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const G1_UNCOMPRESSED: &str = concat!(
+        "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+        "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+    );
+    const G2_COMPRESSED: &str = concat!(
+        "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+    );
+    const G2_UNCOMPRESSED: &str = concat!(
+        "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+        "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+    );
+
+    fn bytes_from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn g1_generator_serialization_matches_test_vectors() {
+        let g = G1Point::generator();
+        assert_eq!(g.to_bytes_compressed(), bytes_from_hex(G1_COMPRESSED));
+        assert_eq!(g.to_bytes_uncompressed(), bytes_from_hex(G1_UNCOMPRESSED));
+    }
+
+    #[test]
+    fn g2_generator_serialization_matches_test_vectors() {
+        let g = G2Point::generator();
+        assert_eq!(g.to_bytes_compressed(), bytes_from_hex(G2_COMPRESSED));
+        assert_eq!(g.to_bytes_uncompressed(), bytes_from_hex(G2_UNCOMPRESSED));
+    }
+
+    #[test]
+    fn negated_generator_sets_the_sign_flag() {
+        let g = -G1Point::generator();
+        assert_eq!(g.to_bytes_compressed()[0], 0xb7);
+    }
+
+    #[test]
+    fn point_at_infinity_serialization() {
+        let mut compressed = vec![0; 48];
+        compressed[0] = 0xc0;
+        let mut uncompressed = vec![0; 96];
+        uncompressed[0] = 0x40;
+        assert_eq!(G1Point::neutral_element().to_bytes_compressed(), compressed);
+        assert_eq!(
+            G1Point::neutral_element().to_bytes_uncompressed(),
+            uncompressed
+        );
+    }
+
+    #[test]
+    fn deserialization_inverts_serialization() {
+        for k in [0u64, 1, 2, 1000] {
+            let p = G1Point::generator().operate_with_self(k);
+            assert_eq!(G1Point::from_bytes(&p.to_bytes_compressed()), Ok(p.clone()));
+            assert_eq!(G1Point::from_bytes(&p.to_bytes_uncompressed()), Ok(p));
+            let q = -G2Point::generator().operate_with_self(k);
+            assert_eq!(G2Point::from_bytes(&q.to_bytes_compressed()), Ok(q.clone()));
+            assert_eq!(G2Point::from_bytes(&q.to_bytes_uncompressed()), Ok(q));
+        }
+        assert_eq!(
+            G2Point::from_bytes(&bytes_from_hex(G2_COMPRESSED)),
+            Ok(G2Point::generator())
+        );
+    }
+
+    #[test]
+    fn deserialization_rejects_non_canonical_encodings() {
+        let compressed = bytes_from_hex(G1_COMPRESSED);
+        // The compression flag over x = p, which reduces to the valid x = 0.
+        let mut unreduced = bytes_from_hex("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+        unreduced[0] |= 0x80;
+        let mut infinity_with_x = compressed.clone();
+        infinity_with_x[0] |= 0x40;
+        let mut uncompressed_with_sign = bytes_from_hex(G1_UNCOMPRESSED);
+        uncompressed_with_sign[0] |= 0x20;
+        for bytes in [
+            &compressed[1..],
+            &unreduced[..],
+            &infinity_with_x[..],
+            &uncompressed_with_sign[..],
+        ] {
+            assert_eq!(
+                G1Point::from_bytes(bytes),
+                Err(EllipticCurveError::InvalidEncoding)
+            );
+        }
+        let mut x_zero = vec![0; 48];
+        x_zero[0] = 0x80;
+        assert!(G1Point::from_bytes(&x_zero).is_ok());
+    }
+
+    #[test]
+    fn deserialization_rejects_points_outside_the_curve() {
+        let mut not_on_the_curve = bytes_from_hex(G1_UNCOMPRESSED);
+        not_on_the_curve[95] ^= 1;
+        assert_eq!(
+            G1Point::from_bytes(&not_on_the_curve),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn generators_have_order_r() {
+        let order = BLS12381ScalarModulus::MODULUS;
+        assert_eq!(
+            G1Point::generator().operate_with_self(order),
+            G1Point::neutral_element()
+        );
+        assert_eq!(
+            G2Point::generator().operate_with_self(order),
+            G2Point::neutral_element()
+        );
+    }
+
+    #[test]
+    fn engine_pairing_is_bilinear() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        assert_eq!(
+            BLS12381::pairing(&p.operate_with_self(5u64), &q.operate_with_self(7u64)),
+            BLS12381::pairing(&p, &q).pow(35u64)
+        );
+    }
+
+    #[test]
+    fn hashed_and_random_points_are_in_the_subgroups() {
+        let points = [
+            G1Point::hash_to_curve(b"message", b"PINOCCHIO-TEST"),
+            G1Point::random(),
+        ];
+        for p in points {
+            assert!(!p.is_neutral_element());
+            assert!(p.is_in_subgroup());
+        }
+        let points = [
+            G2Point::hash_to_curve(b"message", b"PINOCCHIO-TEST"),
+            G2Point::random(),
+        ];
+        for q in points {
+            assert!(!q.is_neutral_element());
+            assert!(q.is_in_subgroup());
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use super::curve::{G1Point, G2Point};
+use super::field_extension::{Fp, Fp12, Fp2, Fp6};
+use crate::math::field::{Field, FrobeniusField};
+
+/// Absolute value of the BLS parameter x = -0xd201000000010000,
+/// which is the loop count of the optimal ate pairing.
+const ATE_LOOP_COUNT: u64 = 0xd201000000010000;
+
+/// Computes the optimal ate pairing between `p` in G1 and `q` in G2.
+/// Returns one if any of them is the point at infinity.
+pub fn pairing(p: &G1Point, q: &G2Point) -> Fp12 {
+    if p.is_neutral_element() || q.is_neutral_element() {
+        Fp12::one()
+    } else {
+        multi_pairing_prepared(&[(&G1Prepared::new(p), &G2Prepared::new(q))])
+    }
+}
+
+/// Computes the product of the optimal ate pairings of every pair `(p, q)`.
+/// The outputs of the Miller loops are multiplied so that the final
+/// exponentiation is done only once. Pairs with the point at infinity are skipped.
+pub fn multi_pairing(pairs: &[(G1Point, G2Point)]) -> Fp12 {
+    let prepared: Vec<_> = pairs
+        .iter()
+        .map(|(p, q)| (G1Prepared::new(p), G2Prepared::new(q)))
+        .collect();
+    let pairs: Vec<_> = prepared.iter().map(|(p, q)| (p, q)).collect();
+    multi_pairing_prepared(&pairs)
+}
+
+/// Same as `multi_pairing`, but with the lines of the points of G2 already computed.
+pub fn multi_pairing_prepared(pairs: &[(&G1Prepared, &G2Prepared)]) -> Fp12 {
+    let product = pairs
+        .iter()
+        .filter_map(|(p, q)| Some(miller_loop(p.point.as_ref()?, q.lines.as_ref()?)))
+        .fold(Fp12::one(), |product, f| product * f);
+    final_exponentiation(&product)
+}
+
+/// A point of G1 in affine coordinates, where the lines of the Miller loop are evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct G1Prepared {
+    /// `None` for the point at infinity.
+    point: Option<(Fp, Fp)>,
+}
+
+impl G1Prepared {
+    pub fn new(p: &G1Point) -> Self {
+        Self {
+            point: (!p.is_neutral_element()).then(|| p.to_affine()),
+        }
+    }
+}
+
+/// The lines of the Miller loop over a point of G2, computed once
+/// so that they can be evaluated at many points of G1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct G2Prepared {
+    /// Coefficients of every line, in the order the Miller loop uses them,
+    /// or `None` for the point at infinity. See `line_and_sum`.
+    lines: Option<Vec<(Fp2, Fp2)>>,
+}
+
+/// Bits of the loop count after the leading one, from the most significant.
+fn loop_bits() -> impl Iterator<Item = bool> {
+    (0..u64::BITS - 1 - ATE_LOOP_COUNT.leading_zeros())
+        .rev()
+        .map(|i| (ATE_LOOP_COUNT >> i) & 1 == 1)
+}
+
+impl G2Prepared {
+    /// Computes the lines through the multiples of `q` in Miller's algorithm over the bits of |x|.
+    /// Vertical lines are skipped since they vanish in the final exponentiation.
+    pub fn new(q: &G2Point) -> Self {
+        if q.is_neutral_element() {
+            return Self { lines: None };
+        }
+        let q = q.to_affine();
+        let mut lines = vec![];
+        let mut t = q.clone();
+        for bit in loop_bits() {
+            let (line, doubled) = line_and_sum(&t, &t).expect("Points of G2 have odd order");
+            lines.push(line);
+            t = doubled;
+            if bit {
+                let (line, sum) =
+                    line_and_sum(&t, &q).expect("The loop never reaches a multiple of the order");
+                lines.push(line);
+                t = sum;
+            }
+        }
+        Self { lines: Some(lines) }
+    }
+}
+
+/// Computes Miller's algorithm, evaluating at `p` the prepared `lines`.
+/// Since x is negative, the result is conjugated at the end, which equals
+/// inverting it up to a factor that vanishes in the final exponentiation.
+/// Lines are evaluated on the twist and mapped to Fp12.
+fn miller_loop(p: &(Fp, Fp), lines: &[(Fp2, Fp2)]) -> Fp12 {
+    let mut lines = lines.iter().map(|line| evaluate_line(line, p));
+    let mut f = Fp12::one();
+    for bit in loop_bits() {
+        f = f.clone() * f * lines.next().expect("There is a line for every step");
+        if bit {
+            f = f * lines.next().expect("There is a line for every step");
+        }
+    }
+    f.conjugate()
+}
+
+/// Returns the coefficients `(slope, slope * xt - yt)` of the line through `t`
+/// and `q` (the tangent if they are equal) together with `t + q`.
+/// Returns `None` when the sum is the point at infinity, in which case the line is vertical.
+fn line_and_sum(t: &(Fp2, Fp2), q: &(Fp2, Fp2)) -> Option<((Fp2, Fp2), (Fp2, Fp2))> {
+    let (xt, yt) = t;
+    let (xq, yq) = q;
+    let slope = if xt != xq {
+        (yq.clone() - yt.clone()) / (xq.clone() - xt.clone())
+    } else if yt == yq && *yt != Fp2::zero() {
+        Fp2::from_u64(3) * xt.clone() * xt.clone() / (Fp2::from_u64(2) * yt.clone())
+    } else {
+        return None;
+    };
+
+    let x = slope.clone() * slope.clone() - xt.clone() - xq.clone();
+    let y = slope.clone() * (xt.clone() - x.clone()) - yt.clone();
+    let constant = slope.clone() * xt.clone() - yt.clone();
+    Some(((slope, constant), (x, y)))
+}
+
+/// Evaluates at `p` the untwisted line with coefficients `(slope, slope * xt - yt)`.
+fn evaluate_line((slope, constant): &(Fp2, Fp2), p: &(Fp, Fp)) -> Fp12 {
+    // On the curve, the slope of the line between the untwisted points is
+    // `slope / w`, so the line evaluated at `p` is
+    //   yp - slope * xp / w + (slope * xt - yt) / w^3.
+    // It is multiplied by w^3, which lies in a proper subfield and therefore
+    // vanishes in the final exponentiation, to get
+    //   (slope * xt - yt) - slope * xp * w^2 + yp * w^3
+    // where w^2 = v and w^3 = v * w.
+    let (xp, yp) = p;
+    Fp12::new(
+        Fp6::new(constant.clone(), -slope.mul_by_base(xp), Fp2::zero()),
+        Fp6::new(Fp2::zero(), Fp2::new_base(*yp), Fp2::zero()),
+    )
+}
+
+/// Raises `f` to the power 3(p^12 - 1) / r, split as
+/// (p^6 - 1) * (p^2 + 1) * (3(p^4 - p^2 + 1) / r).
+/// The conjugate of an element of Fp12 is its p^6 power, and the p^2 power
+/// is a Frobenius map. After the first two steps `f` is in the cyclotomic
+/// subgroup, where squaring is cheaper and the conjugate is the inverse.
+/// Three is coprime to r, so this is still a pairing, and the hard part has
+/// the short chain in x of "Efficient Final Exponentiation via Cyclotomic
+/// Structure for Pairings over Families of Elliptic Curves" by Hayashida,
+/// Hayasaka and Teruya:
+///   3(p^4 - p^2 + 1) / r = (x - 1)^2 (x + p) (x^2 + p^2 - 1) + 3
+fn final_exponentiation(f: &Fp12) -> Fp12 {
+    let f = f.conjugate() * f.inv().unwrap();
+    let f = f.frobenius_power(2) * f;
+
+    // x is negative, so its power is the inverse of the |x| power.
+    let pow_x = |a: &Fp12| a.cyclotomic_pow(ATE_LOOP_COUNT).conjugate();
+    let a = pow_x(&f) * f.conjugate(); // x - 1
+    let b = pow_x(&a) * a.conjugate(); // (x - 1)^2
+    let c = pow_x(&b) * b.frobenius(); // (x - 1)^2 (x + p)
+    let d = pow_x(&pow_x(&c)) * c.frobenius_power(2) * c.conjugate(); // ... (x^2 + p^2 - 1)
+    d * f.cyclotomic_square() * f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::curves::bls12_381::field_extension::BLS12381ScalarModulus;
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::montgomery_field_element::IsModulus;
+    use crate::math::unsigned_integer::UnsignedInteger;
+
+    /// p^2
+    const FIELD_ORDER_SQUARED: UnsignedInteger<12> = UnsignedInteger::from_hex(
+        "0x2a437a4b8c35fc74bd278eaa22f25e9e2dc90e50e7046b466e59e49349e8bd050a62cfd16ddca6ef53149330978ef011d68619c86185c7b292e85a87091a04966bf91ed3e71b743162c338362113cfd7ced6b1d76382eab26aa00001c718e39",
+    );
+
+    /// (p^12 - 1) / r
+    const FINAL_EXPONENT: UnsignedInteger<68> = UnsignedInteger::from_hex(
+        "0x2ee1db5dcc825b7e1bda9c0496a1c0a89ee0193d4977b3f7d4507d07363baa13f8d14a917848517badc3a43d1073776ab353f2c30698e8cc7deada9c0aadff5e9cfee9a074e43b9a660835cc872ee83ff3a0f0f1c0ad0d6106feaf4e347aa68ad49466fa927e7bb9375331807a0dce2630d9aa4b113f414386b0e8819328148978e2b0dd39099b86e1ab656d2670d93e4d7acdd350da5359bc73ab61a0c5bf24c374693c49f570bcd2b01f3077ffb10bf24dde41064837f27611212596bc293c8d4c01f25118790f4684d0b9c40a68eb74bb22a40ee7169cdc1041296532fef459f12438dfc8e2886ef965e61a474c5c85b0129127a1b5ad0463434724538411d1676a53b5a62eb34c05739334f46c02c3f0bd0c55d3109cd15948d0a1fad20044ce6ad4c6bec3ec03ef19592004cedd556952c6d8823b19dadd7c2498345c6e5308f1c511291097db60b1749bf9b71a9f9e0100418a3ef0bc627751bbd81367066bca6a4c1b6dcfc5cceb73fc56947a403577dfa9e13c24ea820b09c1d9f7c31759c3635de3f7a3639991708e88adce88177456c49637fd7961be1a4c7e79fb02faa732e2f3ec2bea83d196283313492caa9d4aff1c910e9622d2a73f62537f2701aaef6539314043f7bbce5b78c7869aeb2181a67e49eeed2161daf3f881bd88592d767f67c4717489119226c2f011d4cab803e9d71650a6f80698e2f8491d12191a04406fbc8fbd5f48925f98630e68bfb24c0bcb9b55df57510",
+    );
+
+    #[test]
+    fn pairing_of_the_generators_has_order_r() {
+        let e = pairing(&G1Point::generator(), &G2Point::generator());
+        assert_ne!(e, Fp12::one());
+        assert_eq!(e.pow(BLS12381ScalarModulus::MODULUS), Fp12::one());
+    }
+
+    #[test]
+    fn pairing_is_bilinear() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let a = 1234567890123u64;
+        let b = 9876543210987u64;
+        let e = pairing(&p.operate_with_self(a), &q.operate_with_self(b));
+        assert_eq!(e, pairing(&p, &q).pow(a as u128 * b as u128));
+        assert_eq!(e, pairing(&p.operate_with_self(a as u128 * b as u128), &q));
+    }
+
+    #[test]
+    fn pairing_with_the_negation_is_the_inverse() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        assert_eq!(pairing(&p, &q) * pairing(&-p, &q), Fp12::one());
+    }
+
+    #[test]
+    fn frobenius_squared_is_the_p_squared_power() {
+        let f = Fp12::random();
+        assert_eq!(f.frobenius_power(2), f.pow(FIELD_ORDER_SQUARED));
+    }
+
+    #[test]
+    fn final_exponentiation_matches_the_naive_one() {
+        let f = Fp12::random();
+        assert_eq!(final_exponentiation(&f), f.pow(FINAL_EXPONENT).pow(3u64));
+    }
+
+    #[test]
+    fn cyclotomic_square_matches_square_after_the_easy_part() {
+        let f = Fp12::random();
+        let f = f.conjugate() * f.inv().unwrap();
+        let f = f.frobenius_power(2) * f;
+        assert_eq!(f.cyclotomic_square(), f.clone() * f.clone());
+        assert_eq!(f.cyclotomic_pow(12345u64), f.pow(12345u64));
+    }
+
+    #[test]
+    fn multi_pairing_is_the_product_of_the_pairings() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let pairs = [
+            (p.operate_with_self(3u64), q.operate_with_self(5u64)),
+            (p.operate_with_self(7u64), q.clone()),
+            (G1Point::neutral_element(), q.clone()),
+        ];
+        let expected = pairs
+            .iter()
+            .fold(Fp12::one(), |product, (p, q)| product * pairing(p, q));
+        assert_eq!(multi_pairing(&pairs), expected);
+        assert_eq!(multi_pairing(&pairs), pairing(&p, &q).pow(22u64));
+    }
+
+    #[test]
+    fn multi_pairing_of_inverse_pairs_is_one() {
+        let p = G1Point::generator().operate_with_self(11u64);
+        let q = G2Point::generator();
+        assert_eq!(
+            multi_pairing(&[(p.clone(), q.clone()), (p.inverse(), q)]),
+            Fp12::one()
+        );
+        assert_eq!(multi_pairing(&[]), Fp12::one());
+    }
+
+    #[test]
+    fn prepared_points_can_be_reused_among_pairings() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let prepared_q = G2Prepared::new(&q);
+        let prepared_ps: Vec<_> = [2u64, 3, 6]
+            .iter()
+            .map(|k| G1Prepared::new(&p.operate_with_self(*k)))
+            .collect();
+        assert_eq!(
+            multi_pairing_prepared(&[
+                (&prepared_ps[0], &prepared_q),
+                (&prepared_ps[1], &prepared_q)
+            ]),
+            pairing(&p, &q).pow(5u64)
+        );
+        assert_eq!(
+            multi_pairing_prepared(&[(&prepared_ps[2], &prepared_q)]),
+            pairing(&p.operate_with_self(6u64), &q)
+        );
+    }
+
+    #[test]
+    fn prepared_point_at_infinity_is_skipped() {
+        let p = G1Prepared::new(&G1Point::generator());
+        let q = G2Prepared::new(&G2Point::generator());
+        let infinity_1 = G1Prepared::new(&G1Point::neutral_element());
+        let infinity_2 = G2Prepared::new(&G2Point::neutral_element());
+        assert_eq!(
+            multi_pairing_prepared(&[(&p, &infinity_2), (&infinity_1, &q)]),
+            Fp12::one()
+        );
+    }
+}
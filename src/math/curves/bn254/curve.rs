@@ -0,0 +1,69 @@
+use super::field_extension::{Fp, Fp2, Fr};
+use crate::math::field::Field;
+use crate::math::short_weierstrass::{ShortWeierstrassParams, ShortWeierstrassPoint};
+use crate::math::unsigned_integer::U256;
+
+/// BN254 curve y^2 = x^3 + 3 over Fp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BN254G1Params;
+impl ShortWeierstrassParams for BN254G1Params {
+    type BaseField = Fp;
+    type ScalarField = Fr;
+    type Cofactor = u64;
+
+    const COFACTOR: u64 = 1;
+
+    fn a() -> Fp {
+        Fp::zero()
+    }
+
+    fn b() -> Fp {
+        Fp::from_u64(3)
+    }
+
+    fn generator_affine() -> (Fp, Fp) {
+        (Fp::from_u64(1), Fp::from_u64(2))
+    }
+}
+
+/// Sextic twist y^2 = x^3 + 3 / (9 + u) over Fp2.
+/// Its points of order r map to G2 through (x, y) -> (x * w^2, y * w^3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BN254G2Params;
+impl ShortWeierstrassParams for BN254G2Params {
+    type BaseField = Fp2;
+    type ScalarField = Fr;
+    type Cofactor = U256;
+
+    /// 2p - r
+    const COFACTOR: U256 =
+        U256::from_hex("0x30644e72e131a029b85045b68181585e06ceecda572a2489345f2299c0f9fa8d");
+
+    fn a() -> Fp2 {
+        Fp2::zero()
+    }
+
+    fn b() -> Fp2 {
+        Fp2::new(
+            Fp::from_hex("2b149d40ceb8aaae81be18991be06ac3b5b4c5e559dbefa33267e6dc24a138e5"),
+            Fp::from_hex("009713b03af0fed4cd2cafadeed8fdf4a74fa084e52d1852e4a2bd0685c315d2"),
+        )
+    }
+
+    /// The generator used by Ethereum's precompiles (EIP-197).
+    fn generator_affine() -> (Fp2, Fp2) {
+        (
+            Fp2::new(
+                Fp::from_hex("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+                Fp::from_hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+            ),
+            Fp2::new(
+                Fp::from_hex("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+                Fp::from_hex("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+            ),
+        )
+    }
+}
+
+pub type G1Point = ShortWeierstrassPoint<BN254G1Params>;
+pub type G2Point = ShortWeierstrassPoint<BN254G2Params>;
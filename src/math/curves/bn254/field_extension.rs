@@ -0,0 +1,121 @@
+use crate::math::cubic_extension::{CubicExtensionElement, CubicExtensionParams};
+use crate::math::field::Field;
+use crate::math::montgomery_field_element::{IsModulus, MontgomeryFieldElement};
+use crate::math::quadratic_extension::{QuadraticExtensionElement, QuadraticExtensionParams};
+use crate::math::unsigned_integer::U256;
+
+/// Order of the base field of BN254.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BN254FieldModulus;
+impl IsModulus<4> for BN254FieldModulus {
+    const MODULUS: U256 =
+        U256::from_hex("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
+}
+
+/// Order of the subgroups G1 and G2 of BN254.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BN254ScalarModulus;
+impl IsModulus<4> for BN254ScalarModulus {
+    const MODULUS: U256 =
+        U256::from_hex("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+}
+
+/// Base field of BN254.
+pub type Fp = MontgomeryFieldElement<BN254FieldModulus, 4>;
+/// Scalar field of BN254.
+pub type Fr = MontgomeryFieldElement<BN254ScalarModulus, 4>;
+
+/// Fp2 = Fp[u] / (u^2 + 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree2ExtensionParams;
+impl QuadraticExtensionParams for Degree2ExtensionParams {
+    type BaseField = Fp;
+
+    fn non_residue() -> Fp {
+        -Fp::one()
+    }
+
+    /// (-1)^((p - 1) / 2) = -1, since p = 3 mod 4.
+    fn frobenius_coefficient() -> Fp {
+        -Fp::one()
+    }
+}
+pub type Fp2 = QuadraticExtensionElement<Degree2ExtensionParams>;
+
+/// Fp6 = Fp2[v] / (v^3 - (9 + u))
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree6ExtensionParams;
+impl CubicExtensionParams for Degree6ExtensionParams {
+    type BaseField = Fp2;
+
+    fn non_residue() -> Fp2 {
+        Fp2::new(Fp::from_u64(9), Fp::one())
+    }
+
+    /// xi^((p - 1) / 3)
+    fn frobenius_coefficient() -> Fp2 {
+        Fp2::new(
+            Fp::from_hex("0x2fb347984f7911f74c0bec3cf559b143b78cc310c2c3330c99e39557176f553d"),
+            Fp::from_hex("0x16c9e55061ebae204ba4cc8bd75a079432ae2a1d0b7c9dce1665d51c640fcba2"),
+        )
+    }
+}
+pub type Fp6 = CubicExtensionElement<Degree6ExtensionParams>;
+
+/// Fp12 = Fp6[w] / (w^2 - v)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degree12ExtensionParams;
+impl QuadraticExtensionParams for Degree12ExtensionParams {
+    type BaseField = Fp6;
+
+    fn non_residue() -> Fp6 {
+        Fp6::new(Fp2::zero(), Fp2::one(), Fp2::zero())
+    }
+
+    /// v^((p - 1) / 2) = xi^((p - 1) / 6), since p = 1 mod 6.
+    fn frobenius_coefficient() -> Fp6 {
+        Fp6::new_base(Fp2::new(
+            Fp::from_hex("0x1284b71c2865a7dfe8b99fdd76e68b605c521e08292f2176d60b35dadcc9e470"),
+            Fp::from_hex("0x246996f3b4fae7e6a6327cfe12150b8e747992778eeec7e5ca5cf05f80f362ac"),
+        ))
+    }
+}
+pub type Fp12 = QuadraticExtensionElement<Degree12ExtensionParams>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::field::FrobeniusField;
+
+    #[test]
+    fn w_to_the_sixth_is_the_cubic_non_residue() {
+        let w = Fp12::new(Fp6::zero(), Fp6::one());
+        let xi = Degree6ExtensionParams::non_residue();
+        assert_eq!(w.pow(6u64), Fp12::new_base(Fp6::new_base(xi)));
+    }
+
+    #[test]
+    fn fp12_inverse_works() {
+        let a = Fp12::random();
+        assert_eq!(a.clone() * a.inv().unwrap(), Fp12::one());
+    }
+
+    #[test]
+    fn frobenius_is_the_p_power_in_every_level_of_the_tower() {
+        let p = BN254FieldModulus::MODULUS;
+        let a = Fp2::random();
+        assert_eq!(a.frobenius(), a.pow(p));
+        let b = Fp6::random();
+        assert_eq!(b.frobenius(), b.pow(p));
+        let c = Fp12::random();
+        assert_eq!(c.frobenius(), c.pow(p));
+        assert_eq!(c.frobenius_power(12), c);
+    }
+
+    #[test]
+    fn fp12_has_degree_12_and_its_conjugate_is_the_p_to_the_sixth_power() {
+        assert_eq!(Fp12::EXTENSION_DEGREE, 12);
+        let c = Fp12::random();
+        assert_eq!(c.frobenius_power(6), c.conjugate());
+    }
+}
@@ -0,0 +1,253 @@
+//! BN254 (also known as alt_bn128), the curve behind Ethereum's pairing precompiles.
+//! See "Pairing-Friendly Elliptic Curves of Prime Order" by Barreto and Naehrig.
+pub mod curve;
+pub mod field_extension;
+pub mod pairing;
+
+use self::curve::{G1Point, G2Point};
+use self::field_extension::{Fp12, Fr};
+use crate::math::cyclic_group::PairingEngine;
+
+/// The optimal ate pairing of BN254, mapping G1 x G2 to the
+/// subgroup of order r of Fp12.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BN254;
+
+impl PairingEngine for BN254 {
+    type Scalar = Fr;
+    type G1 = G1Point;
+    type G2 = G2Point;
+    type GT = Fp12;
+    type G1Prepared = pairing::G1Prepared;
+    type G2Prepared = pairing::G2Prepared;
+
+    fn pairing(p: &G1Point, q: &G2Point) -> Fp12 {
+        pairing::pairing(p, q)
+    }
+
+    fn multi_pairing(pairs: &[(G1Point, G2Point)]) -> Fp12 {
+        pairing::multi_pairing(pairs)
+    }
+
+    fn prepare_g1(p: &G1Point) -> pairing::G1Prepared {
+        pairing::G1Prepared::new(p)
+    }
+
+    fn prepare_g2(q: &G2Point) -> pairing::G2Prepared {
+        pairing::G2Prepared::new(q)
+    }
+
+    fn multi_pairing_prepared(pairs: &[(&pairing::G1Prepared, &pairing::G2Prepared)]) -> Fp12 {
+        pairing::multi_pairing_prepared(pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::field_extension::{BN254ScalarModulus, Fp, Fp2};
+    use super::*;
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::field::Field;
+    use crate::math::montgomery_field_element::IsModulus;
+    use crate::math::short_weierstrass::{ShortWeierstrassParams, ShortWeierstrassPoint};
+
+    fn bytes_from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Runs the `ecPairing` precompile of EIP-197 on `input`, the concatenation
+    /// of pairs of a point of G1 and a point of G2 in affine coordinates, where
+    /// (0, 0) is the point at infinity. Returns whether the product of the
+    /// pairings is one.
+    fn ec_pairing(input: &str) -> bool {
+        fn point<P: ShortWeierstrassParams>(bytes: &[u8]) -> ShortWeierstrassPoint<P> {
+            if bytes.iter().all(|&byte| byte == 0) {
+                return ShortWeierstrassPoint::neutral_element();
+            }
+            let (x, y) = bytes.split_at(bytes.len() / 2);
+            let x = P::BaseField::from_bytes(x).unwrap();
+            let y = P::BaseField::from_bytes(y).unwrap();
+            let point = ShortWeierstrassPoint::from_affine(x, y).unwrap();
+            assert!(point.is_in_subgroup());
+            point
+        }
+        let input = bytes_from_hex(input);
+        assert!(input.len().is_multiple_of(192));
+        let pairs: Vec<(G1Point, G2Point)> = input
+            .chunks(192)
+            .map(|pair| (point(&pair[..64]), point(&pair[64..])))
+            .collect();
+        BN254::multi_pairing(&pairs) == Fp12::one()
+    }
+
+    // Test vectors of the `ecPairing` precompile from go-ethereum,
+    // core/vm/testdata/precompiles/bn256Pairing.json.
+
+    #[test]
+    fn ec_pairing_of_jeff1_is_one() {
+        assert!(ec_pairing(concat!(
+            "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59",
+            "3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41",
+            "209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7",
+            "04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678",
+            "2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d",
+            "120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550",
+            "111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c",
+            "2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411",
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        )));
+    }
+
+    #[test]
+    fn ec_pairing_of_two_point_match_2_is_one() {
+        assert!(ec_pairing(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45",
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        )));
+    }
+
+    #[test]
+    fn ec_pairing_of_one_point_is_not_one() {
+        assert!(!ec_pairing(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        )));
+    }
+
+    #[test]
+    fn ec_pairing_of_the_empty_input_is_one() {
+        assert!(ec_pairing(""));
+    }
+
+    #[test]
+    fn g1_generator_encoding_matches_eip_197() {
+        let (x, y) = G1Point::generator().to_affine();
+        let mut expected_x = vec![0; 32];
+        expected_x[31] = 1;
+        let mut expected_y = vec![0; 32];
+        expected_y[31] = 2;
+        assert_eq!(x.to_bytes(), expected_x);
+        assert_eq!(y.to_bytes(), expected_y);
+    }
+
+    #[test]
+    fn g2_generator_encoding_matches_eip_197() {
+        // EIP-197 encodes an element a * i + b of Fp2 as (a, b).
+        let (x, y) = G2Point::generator().to_affine();
+        assert_eq!(
+            x.to_bytes(),
+            bytes_from_hex(concat!(
+                "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+                "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"
+            ))
+        );
+        assert_eq!(
+            y.to_bytes(),
+            bytes_from_hex(concat!(
+                "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+                "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+            ))
+        );
+    }
+
+    #[test]
+    fn point_encodings_round_trip_with_a_flags_byte() {
+        // The 254 bit field leaves two free bits, too few for the three flags.
+        let g = G1Point::generator();
+        let mut compressed = vec![0; 33];
+        compressed[0] = 0x80;
+        compressed[32] = 1;
+        assert_eq!(g.to_bytes_compressed(), compressed);
+        for k in [0u64, 1, 1000] {
+            let p = G1Point::generator().operate_with_self(k);
+            assert_eq!(G1Point::from_bytes(&p.to_bytes_compressed()), Ok(p.clone()));
+            assert_eq!(G1Point::from_bytes(&p.to_bytes_uncompressed()), Ok(p));
+            let q = G2Point::generator().operate_with_self(k);
+            assert_eq!(G2Point::from_bytes(&q.to_bytes_compressed()), Ok(q.clone()));
+            assert_eq!(G2Point::from_bytes(&q.to_bytes_uncompressed()), Ok(q));
+        }
+    }
+
+    #[test]
+    fn generators_have_order_r() {
+        let order = BN254ScalarModulus::MODULUS;
+        assert_eq!(
+            G1Point::generator().operate_with_self(order),
+            G1Point::neutral_element()
+        );
+        assert_eq!(
+            G2Point::generator().operate_with_self(order),
+            G2Point::neutral_element()
+        );
+    }
+
+    #[test]
+    fn scalar_multiplication_by_minus_one_is_the_negation() {
+        let minus_one = -Fr::one();
+        assert_eq!(
+            G1Point::generator().operate_with_scalar(&minus_one),
+            -G1Point::generator()
+        );
+        assert_eq!(
+            G2Point::generator().operate_with_scalar(&minus_one),
+            -G2Point::generator()
+        );
+    }
+
+    #[test]
+    fn g2_generator_lies_on_the_twist() {
+        let (x, y) = G2Point::generator().to_affine();
+        let b = Fp2::from_u64(3) / Fp2::new(Fp::from_u64(9), Fp::one());
+        assert_eq!(y.clone() * y, x.pow(3u64) + b);
+    }
+
+    #[test]
+    fn engine_pairing_is_bilinear() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        assert_eq!(
+            BN254::pairing(&p.operate_with_self(5u64), &q.operate_with_self(7u64)),
+            BN254::pairing(&p, &q).pow(35u64)
+        );
+    }
+
+    #[test]
+    fn hashed_and_random_points_are_in_the_subgroups() {
+        let points = [
+            G1Point::hash_to_curve(b"message", b"PINOCCHIO-TEST"),
+            G1Point::random(),
+        ];
+        for p in points {
+            assert!(!p.is_neutral_element());
+            assert!(p.is_in_subgroup());
+        }
+        let points = [
+            G2Point::hash_to_curve(b"message", b"PINOCCHIO-TEST"),
+            G2Point::random(),
+        ];
+        for q in points {
+            assert!(!q.is_neutral_element());
+            assert!(q.is_in_subgroup());
+        }
+    }
+}
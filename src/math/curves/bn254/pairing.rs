@@ -0,0 +1,339 @@
+use super::curve::{G1Point, G2Point};
+use super::field_extension::{Degree12ExtensionParams, Degree6ExtensionParams, Fp, Fp12, Fp2, Fp6};
+use crate::math::cubic_extension::CubicExtensionParams;
+use crate::math::field::{Field, FrobeniusField};
+use crate::math::quadratic_extension::QuadraticExtensionParams;
+
+/// The BN parameter of the curve.
+const X: u64 = 4965661367192848881;
+
+/// Parameter `6x + 2` of the optimal ate pairing.
+const ATE_LOOP_COUNT: u128 = 29793968203157093288;
+
+/// Computes the optimal ate pairing between `p` in G1 and `q` in G2.
+/// Returns one if any of them is the point at infinity.
+pub fn pairing(p: &G1Point, q: &G2Point) -> Fp12 {
+    if p.is_neutral_element() || q.is_neutral_element() {
+        Fp12::one()
+    } else {
+        multi_pairing_prepared(&[(&G1Prepared::new(p), &G2Prepared::new(q))])
+    }
+}
+
+/// Computes the product of the optimal ate pairings of every pair `(p, q)`.
+/// The outputs of the Miller loops are multiplied so that the final
+/// exponentiation is done only once. Pairs with the point at infinity are skipped.
+pub fn multi_pairing(pairs: &[(G1Point, G2Point)]) -> Fp12 {
+    let prepared: Vec<_> = pairs
+        .iter()
+        .map(|(p, q)| (G1Prepared::new(p), G2Prepared::new(q)))
+        .collect();
+    let pairs: Vec<_> = prepared.iter().map(|(p, q)| (p, q)).collect();
+    multi_pairing_prepared(&pairs)
+}
+
+/// Same as `multi_pairing`, but with the lines of the points of G2 already computed.
+pub fn multi_pairing_prepared(pairs: &[(&G1Prepared, &G2Prepared)]) -> Fp12 {
+    let product = pairs
+        .iter()
+        .filter_map(|(p, q)| Some(miller_loop(p.point.as_ref()?, q.lines.as_ref()?)))
+        .fold(Fp12::one(), |product, f| product * f);
+    final_exponentiation(&product)
+}
+
+/// A point of G1 in affine coordinates, where the lines of the Miller loop are evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct G1Prepared {
+    /// `None` for the point at infinity.
+    point: Option<(Fp, Fp)>,
+}
+
+impl G1Prepared {
+    pub fn new(p: &G1Point) -> Self {
+        Self {
+            point: (!p.is_neutral_element()).then(|| p.to_affine()),
+        }
+    }
+}
+
+/// The lines of the Miller loop over a point of G2, computed once
+/// so that they can be evaluated at many points of G1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct G2Prepared {
+    /// Coefficients of every line, in the order the Miller loop uses them,
+    /// or `None` for the point at infinity. See `line_and_sum`.
+    lines: Option<Vec<(Fp2, Fp2)>>,
+}
+
+/// Bits of the loop count after the leading one, from the most significant.
+fn loop_bits() -> impl Iterator<Item = bool> {
+    (0..u128::BITS - 1 - ATE_LOOP_COUNT.leading_zeros())
+        .rev()
+        .map(|i| (ATE_LOOP_COUNT >> i) & 1 == 1)
+}
+
+impl G2Prepared {
+    /// Computes the lines through the multiples of `q` in Miller's algorithm over the bits
+    /// of `6x + 2`, followed by the two extra lines with the Frobenius images of `q`.
+    /// Vertical lines are skipped since they vanish in the final exponentiation.
+    /// See "High-Speed Software Implementation of the Optimal Ate Pairing
+    /// over Barreto–Naehrig Curves", Algorithm 1.
+    pub fn new(q: &G2Point) -> Self {
+        if q.is_neutral_element() {
+            return Self { lines: None };
+        }
+        let q = q.to_affine();
+        let mut lines = vec![];
+        let mut t = q.clone();
+        for bit in loop_bits() {
+            let (line, doubled) = line_and_sum(&t, &t).expect("Points of G2 have odd order");
+            lines.push(line);
+            t = doubled;
+            if bit {
+                let (line, sum) =
+                    line_and_sum(&t, &q).expect("The loop never reaches a multiple of the order");
+                lines.push(line);
+                t = sum;
+            }
+        }
+
+        let q1 = frobenius(&q);
+        let q2 = frobenius(&q1);
+        let (line, sum) =
+            line_and_sum(&t, &q1).expect("The loop never reaches a multiple of the order");
+        lines.push(line);
+        lines.extend(line_and_sum(&sum, &(q2.0, -q2.1)).map(|(line, _)| line));
+        Self { lines: Some(lines) }
+    }
+}
+
+/// Computes Miller's algorithm, evaluating at `p` the prepared `lines`.
+/// Lines are evaluated on the twist and mapped to Fp12.
+fn miller_loop(p: &(Fp, Fp), lines: &[(Fp2, Fp2)]) -> Fp12 {
+    let mut lines = lines.iter().map(|line| evaluate_line(line, p));
+    let mut f = Fp12::one();
+    for bit in loop_bits() {
+        f = f.clone() * f * lines.next().expect("There is a line for every step");
+        if bit {
+            f = f * lines.next().expect("There is a line for every step");
+        }
+    }
+    // The lines with the Frobenius images of `q`.
+    lines.fold(f, |f, line| f * line)
+}
+
+/// Returns the coefficients `(slope, slope * xt - yt)` of the line through `t`
+/// and `q` (the tangent if they are equal) together with `t + q`.
+/// Returns `None` when the sum is the point at infinity, in which case the line is vertical.
+fn line_and_sum(t: &(Fp2, Fp2), q: &(Fp2, Fp2)) -> Option<((Fp2, Fp2), (Fp2, Fp2))> {
+    let (xt, yt) = t;
+    let (xq, yq) = q;
+    let slope = if xt != xq {
+        (yq.clone() - yt.clone()) / (xq.clone() - xt.clone())
+    } else if yt == yq && *yt != Fp2::zero() {
+        Fp2::from_u64(3) * xt.clone() * xt.clone() / (Fp2::from_u64(2) * yt.clone())
+    } else {
+        return None;
+    };
+
+    let x = slope.clone() * slope.clone() - xt.clone() - xq.clone();
+    let y = slope.clone() * (xt.clone() - x.clone()) - yt.clone();
+    let constant = slope.clone() * xt.clone() - yt.clone();
+    Some(((slope, constant), (x, y)))
+}
+
+/// Evaluates at `p` the untwisted line with coefficients `(slope, slope * xt - yt)`.
+fn evaluate_line((slope, constant): &(Fp2, Fp2), p: &(Fp, Fp)) -> Fp12 {
+    // On the curve, the slope of the line between the untwisted points is
+    // `slope * w`, so the line evaluated at `p` is
+    //   yp - slope * xp * w + (slope * xt - yt) * w^3
+    // where w^3 = v * w.
+    let (xp, yp) = p;
+    Fp12::new(
+        Fp6::new_base(Fp2::new_base(*yp)),
+        Fp6::new(-slope.mul_by_base(xp), constant.clone(), Fp2::zero()),
+    )
+}
+
+/// Applies the p-power Frobenius endomorphism to a point of the twist:
+/// (x, y) -> (x^p * xi^((p - 1) / 3), y^p * xi^((p - 1) / 2))
+/// where xi^((p - 1) / 2) = xi^((p - 1) / 3) * xi^((p - 1) / 6).
+fn frobenius((x, y): &(Fp2, Fp2)) -> (Fp2, Fp2) {
+    let x_coefficient = Degree6ExtensionParams::frobenius_coefficient();
+    let y_coefficient = x_coefficient.clone()
+        * Degree12ExtensionParams::frobenius_coefficient()
+            .c0()
+            .clone();
+    (x.frobenius() * x_coefficient, y.frobenius() * y_coefficient)
+}
+
+/// Raises `f` to the power 2x(6x^2 + 3x + 1)(p^12 - 1) / r, split as
+/// (p^6 - 1) * (p^2 + 1) * (2x(6x^2 + 3x + 1)(p^4 - p^2 + 1) / r).
+/// The conjugate of an element of Fp12 is its p^6 power, and the p^2 power
+/// is a Frobenius map. After the first two steps `f` is in the cyclotomic
+/// subgroup, where squaring is cheaper and the conjugate is the inverse.
+/// The multiple of the hard part is coprime to r, so this is still a pairing,
+/// and it has the short chain in x of "Faster Hashing to G2" by Fuentes-Castañeda,
+/// Knapp and Rodríguez-Henríquez, section 6:
+///   p^3 (12x^3 + 6x^2 + 4x - 1) + p^2 (12x^3 + 6x^2 + 6x)
+///     + p (12x^3 + 6x^2 + 4x) + (12x^3 + 12x^2 + 6x + 1)
+fn final_exponentiation(f: &Fp12) -> Fp12 {
+    let f = f.conjugate() * f.inv().unwrap();
+    let f = f.frobenius_power(2) * f;
+
+    let pow_x = |a: &Fp12| a.cyclotomic_pow(X);
+    let a = pow_x(&f); // x
+    let b = a.cyclotomic_square(); // 2x
+    let c = b.cyclotomic_square() * b.clone(); // 6x
+    let d = pow_x(&c); // 6x^2
+    let e = pow_x(&d.cyclotomic_square()); // 12x^3
+    let t2 = e * d.clone() * c; // 12x^3 + 6x^2 + 6x
+    let t1 = t2.clone() * b.conjugate(); // 12x^3 + 6x^2 + 4x
+    let t3 = t1.clone() * f.conjugate(); // 12x^3 + 6x^2 + 4x - 1
+    let t0 = t2.clone() * d * f; // 12x^3 + 12x^2 + 6x + 1
+    t3.frobenius_power(3) * t2.frobenius_power(2) * t1.frobenius() * t0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::curves::bn254::field_extension::BN254ScalarModulus;
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::montgomery_field_element::IsModulus;
+    use crate::math::unsigned_integer::UnsignedInteger;
+
+    /// p^2
+    const FIELD_ORDER_SQUARED: UnsignedInteger<8> = UnsignedInteger::from_hex(
+        "0x925c4b8763cbf9c599a6f7c0348d21cb00b85511637560626edfa5c34c6b38d04689e957a1242c84a50189c6d96cadca602072d09eac1013b5458a2275d69b1",
+    );
+
+    /// (p^12 - 1) / r
+    const FINAL_EXPONENT: UnsignedInteger<44> = UnsignedInteger::from_hex(
+        "0x2f4b6dc97020fddadf107d20bc842d43bf6369b1ff6a1c71015f3f7be2e1e30a73bb94fec0daf15466b2383a5d3ec3d15ad524d8f70c54efee1bd8c3b21377e563a09a1b705887e72eceaddea3790364a61f676baaf977870e88d5c6c8fef0781361e443ae77f5b63a2a2264487f2940a8b1ddb3d15062cd0fb2015dfc6668449aed3cc48a82d0d602d268c7daab6a41294c0cc4ebe5664568dfc50e1648a45a4a1e3a5195846a3ed011a337a02088ec80e0ebae8755cfe107acf3aafb40494e406f804216bb10cf430b0f37856b42db8dc5514724ee93dfb10826f0dd4a0364b9580291d2cd65664814fde37ca80bb4ea44eacc5e641bbadf423f9a2cbf813b8d145da90029baee7ddadda71c7f3811c4105262945bba1668c3be69a3c230974d83561841d766f9c9d570bb7fbe04c7e8a6c3c760c0de81def35692da361102b6b9b2b918837fa97896e84abb40a4efb7e54523a486964b64ca86f120",
+    );
+
+    #[test]
+    fn pairing_of_the_generators_has_order_r() {
+        let e = pairing(&G1Point::generator(), &G2Point::generator());
+        assert_ne!(e, Fp12::one());
+        assert_eq!(e.pow(BN254ScalarModulus::MODULUS), Fp12::one());
+    }
+
+    #[test]
+    fn pairing_is_bilinear() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let a = 1234567890123u64;
+        let b = 9876543210987u64;
+        let e = pairing(&p.operate_with_self(a), &q.operate_with_self(b));
+        assert_eq!(e, pairing(&p, &q).pow(a as u128 * b as u128));
+        assert_eq!(e, pairing(&p.operate_with_self(a as u128 * b as u128), &q));
+    }
+
+    #[test]
+    fn pairing_with_the_negation_is_the_inverse() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        assert_eq!(pairing(&p, &q) * pairing(&-p, &q), Fp12::one());
+    }
+
+    #[test]
+    fn pairing_with_the_point_at_infinity_is_one() {
+        assert_eq!(
+            pairing(&G1Point::neutral_element(), &G2Point::generator()),
+            Fp12::one()
+        );
+        assert_eq!(
+            pairing(&G1Point::generator(), &G2Point::neutral_element()),
+            Fp12::one()
+        );
+    }
+
+    #[test]
+    fn frobenius_squared_is_the_p_squared_power() {
+        let f = Fp12::random();
+        assert_eq!(f.frobenius_power(2), f.pow(FIELD_ORDER_SQUARED));
+    }
+
+    #[test]
+    fn final_exponentiation_matches_the_naive_one() {
+        let f = Fp12::random();
+        // 2x(6x^2 + 3x + 1), split in two factors that fit in a u128.
+        let x = X as u128;
+        let multiple = (2 * x, 6 * x * x + 3 * x + 1);
+        assert_eq!(
+            final_exponentiation(&f),
+            f.pow(FINAL_EXPONENT).pow(multiple.0).pow(multiple.1)
+        );
+    }
+
+    #[test]
+    fn cyclotomic_square_matches_square_after_the_easy_part() {
+        let f = Fp12::random();
+        let f = f.conjugate() * f.inv().unwrap();
+        let f = f.frobenius_power(2) * f;
+        assert_eq!(f.cyclotomic_square(), f.clone() * f.clone());
+        assert_eq!(f.cyclotomic_pow(12345u64), f.pow(12345u64));
+    }
+
+    #[test]
+    fn multi_pairing_is_the_product_of_the_pairings() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let pairs = [
+            (p.operate_with_self(3u64), q.operate_with_self(5u64)),
+            (p.operate_with_self(7u64), q.clone()),
+            (G1Point::neutral_element(), q.clone()),
+        ];
+        let expected = pairs
+            .iter()
+            .fold(Fp12::one(), |product, (p, q)| product * pairing(p, q));
+        assert_eq!(multi_pairing(&pairs), expected);
+        assert_eq!(multi_pairing(&pairs), pairing(&p, &q).pow(22u64));
+    }
+
+    #[test]
+    fn multi_pairing_of_inverse_pairs_is_one() {
+        let p = G1Point::generator().operate_with_self(11u64);
+        let q = G2Point::generator();
+        assert_eq!(
+            multi_pairing(&[(p.clone(), q.clone()), (p.inverse(), q)]),
+            Fp12::one()
+        );
+        assert_eq!(multi_pairing(&[]), Fp12::one());
+    }
+
+    #[test]
+    fn prepared_points_can_be_reused_among_pairings() {
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+        let prepared_q = G2Prepared::new(&q);
+        let prepared_ps: Vec<_> = [2u64, 3, 6]
+            .iter()
+            .map(|k| G1Prepared::new(&p.operate_with_self(*k)))
+            .collect();
+        assert_eq!(
+            multi_pairing_prepared(&[
+                (&prepared_ps[0], &prepared_q),
+                (&prepared_ps[1], &prepared_q)
+            ]),
+            pairing(&p, &q).pow(5u64)
+        );
+        assert_eq!(
+            multi_pairing_prepared(&[(&prepared_ps[2], &prepared_q)]),
+            pairing(&p.operate_with_self(6u64), &q)
+        );
+    }
+
+    #[test]
+    fn prepared_point_at_infinity_is_skipped() {
+        let p = G1Prepared::new(&G1Point::generator());
+        let q = G2Prepared::new(&G2Point::generator());
+        let infinity_1 = G1Prepared::new(&G1Point::neutral_element());
+        let infinity_2 = G2Prepared::new(&G2Point::neutral_element());
+        assert_eq!(
+            multi_pairing_prepared(&[(&p, &infinity_2), (&infinity_1, &q)]),
+            Fp12::one()
+        );
+    }
+}
@@ -0,0 +1,6 @@
+//! Predefined curves: parameter sets for `EllipticCurveElement`
+//! and production-size pairing-friendly curves with their own point types.
+pub mod bls12_381;
+pub mod bn254;
+pub mod search;
+pub mod supersingular;
@@ -0,0 +1,591 @@
+//! Search for small pairing-friendly curves, for teaching and testing.
+//! Arithmetic is done directly on `u64` values, since the field orders are only
+//! known at runtime, while `FieldElement` takes them as a const parameter.
+//! Supersingular results can be printed as a `CurveParams` implementation ready
+//! to be pasted in `supersingular.rs`.
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Family of curves to search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveFamily {
+    /// y^2 = x^3 + x over a prime field of order p = 3 mod 4. They have p + 1 points,
+    /// embedding degree 2 and the distortion map used by `EllipticCurveElement`.
+    Supersingular,
+    /// Ordinary curves y^2 = x^3 + a * x + b, found by exhaustive search, whose largest
+    /// subgroup of prime order has the chosen embedding degree. Like MNT curves, but
+    /// the cofactor is allowed to be up to `SearchParameters::max_cofactor`.
+    Ordinary,
+}
+
+/// Algorithm used to count the points of a curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointCounting {
+    /// Adds up the number of square roots of x^3 + a * x + b for every x.
+    Naive,
+    /// Finds the multiple of the orders of some points that lies in the Hasse interval,
+    /// falling back to the naive method when there is more than one.
+    BabyStepGiantStep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchParameters {
+    pub family: CurveFamily,
+    pub embedding_degree: u32,
+    /// Bit sizes allowed for the subgroup order r, both ends included.
+    pub subgroup_bits: RangeInclusive<u32>,
+    /// Largest bit size of the base field order p.
+    pub max_field_bits: u32,
+    /// Largest number of points of the curve divided by r.
+    pub max_cofactor: u64,
+    pub point_counting: PointCounting,
+    /// The search stops after finding this many curves.
+    pub max_results: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SearchError {
+    /// Supersingular curves y^2 = x^3 + x over p = 3 mod 4 have embedding degree 2.
+    UnsupportedEmbeddingDegree,
+    /// The search is exhaustive, so the field order is limited to 32 bits.
+    FieldTooLarge,
+}
+
+/// Parameters of a curve y^2 = x^3 + a * x + b over the prime field of order `p`,
+/// with a subgroup of prime order `r` generated by `generator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveParameters {
+    pub family: CurveFamily,
+    pub p: u64,
+    pub a: u64,
+    pub b: u64,
+    pub number_of_points: u64,
+    pub r: u64,
+    pub cofactor: u64,
+    pub embedding_degree: u32,
+    pub generator: (u64, u64),
+}
+
+impl CurveParameters {
+    /// Returns the implementation of `CurveParams` for a supersingular curve,
+    /// in the style of `supersingular.rs`. Ordinary curves have no distortion map,
+    /// so `None` is returned for them.
+    pub fn to_curve_params(&self) -> Option<String> {
+        if self.family != CurveFamily::Supersingular {
+            return None;
+        }
+        let Self {
+            p,
+            r,
+            cofactor,
+            generator: (x, y),
+            ..
+        } = self;
+        Some(format!(
+            "/// y^2 = x^3 + x over F_{p}, with a subgroup of order {r}.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve{p};
+
+impl CurveParams for Curve{p} {{
+    type BaseField = FieldElement<{p}>;
+    type ScalarField = FieldElement<{r}>;
+
+    const ORDER_P: u128 = {p};
+    const ORDER_R: u128 = {r};
+    const COFACTOR: u128 = {cofactor};
+    const EMBEDDING_DEGREE: u32 = 2;
+
+    fn a() -> Self::BaseField {{
+        FieldElement::new(1)
+    }}
+
+    fn b() -> Self::BaseField {{
+        FieldElement::new(0)
+    }}
+
+    fn generator_affine() -> (Self::BaseField, Self::BaseField) {{
+        (FieldElement::new({x}), FieldElement::new({y}))
+    }}
+
+    fn distortion_map(
+        coordinates: &[FieldExtensionElement<Self::BaseField>; 3],
+    ) -> [FieldExtensionElement<Self::BaseField>; 3] {{
+        distortion_map(coordinates)
+    }}
+}}
+"
+        ))
+    }
+}
+
+impl fmt::Display for CurveParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "y^2 = x^3 + {} * x + {} over F_{}: {} points, r = {}, cofactor = {}, k = {}, generator = {:?}",
+            self.a,
+            self.b,
+            self.p,
+            self.number_of_points,
+            self.r,
+            self.cofactor,
+            self.embedding_degree,
+            self.generator
+        )
+    }
+}
+
+/// Returns the curves of `parameters.family` that satisfy `parameters`,
+/// by increasing order of the base field.
+pub fn search(parameters: &SearchParameters) -> Result<Vec<CurveParameters>, SearchError> {
+    if parameters.max_field_bits > 32 {
+        return Err(SearchError::FieldTooLarge);
+    }
+    if parameters.family == CurveFamily::Supersingular && parameters.embedding_degree != 2 {
+        return Err(SearchError::UnsupportedEmbeddingDegree);
+    }
+    let mut results = vec![];
+    for p in (5..1u64 << parameters.max_field_bits).filter(|p| is_prime(*p)) {
+        let coefficients: Vec<(u64, u64)> = match parameters.family {
+            CurveFamily::Supersingular if p % 4 == 3 => vec![(1, 0)],
+            CurveFamily::Supersingular => vec![],
+            CurveFamily::Ordinary => (0..p).flat_map(|a| (0..p).map(move |b| (a, b))).collect(),
+        };
+        for (a, b) in coefficients {
+            let curve = Curve { p, a, b };
+            if let Some(found) = curve.pairing_parameters(parameters) {
+                results.push(found);
+                if results.len() == parameters.max_results {
+                    return Ok(results);
+                }
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Affine point, or `None` for the point at infinity.
+type Point = Option<(u64, u64)>;
+
+/// The curve y^2 = x^3 + a * x + b over the prime field of order `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Curve {
+    p: u64,
+    a: u64,
+    b: u64,
+}
+
+impl Curve {
+    /// Returns the parameters of the curve if it satisfies `parameters`.
+    fn pairing_parameters(&self, parameters: &SearchParameters) -> Option<CurveParameters> {
+        let Curve { p, a, b } = *self;
+        if self.is_singular() {
+            return None;
+        }
+        let number_of_points = self.count_points(parameters.point_counting);
+        let supersingular = number_of_points % p == 1;
+        if supersingular != (parameters.family == CurveFamily::Supersingular) {
+            return None;
+        }
+        let r = largest_prime_factor(number_of_points);
+        let cofactor = number_of_points / r;
+        let embedding_degree = multiplicative_order(p % r, r)?;
+        if r == p
+            || !parameters.subgroup_bits.contains(&(64 - r.leading_zeros()))
+            || cofactor > parameters.max_cofactor
+            || embedding_degree != parameters.embedding_degree
+        {
+            return None;
+        }
+        let generator = self
+            .points()
+            .map(|point| self.multiply(point, cofactor))
+            .find(|point| point.is_some())??;
+        Some(CurveParameters {
+            family: parameters.family,
+            p,
+            a,
+            b,
+            number_of_points,
+            r,
+            cofactor,
+            embedding_degree,
+            generator,
+        })
+    }
+
+    /// Returns `true` if 4 * a^3 + 27 * b^2 = 0, in which case it is not an elliptic curve.
+    fn is_singular(&self) -> bool {
+        (4 * pow_mod(self.a, 3, self.p) + 27 * mul_mod(self.b, self.b, self.p))
+            .is_multiple_of(self.p)
+    }
+
+    fn rhs(&self, x: u64) -> u64 {
+        (pow_mod(x, 3, self.p) + mul_mod(self.a, x, self.p) + self.b) % self.p
+    }
+
+    /// Returns the affine points of the curve, with the smallest of the two y for every x.
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.p).filter_map(|x| sqrt_mod(self.rhs(x), self.p).map(|y| Some((x, y))))
+    }
+
+    fn count_points(&self, method: PointCounting) -> u64 {
+        match method {
+            PointCounting::Naive => self.count_points_naive(),
+            PointCounting::BabyStepGiantStep => self.count_points_bsgs(),
+        }
+    }
+
+    fn count_points_naive(&self) -> u64 {
+        // The point at infinity plus 1 + legendre(x^3 + a * x + b) points for every x.
+        (0..self.p).fold(1, |count, x| match legendre(self.rhs(x), self.p) {
+            0 => count + 1,
+            1 => count + 2,
+            _ => count,
+        })
+    }
+
+    /// Keeps the least common multiple of the orders of some points until it has a
+    /// single multiple in the Hasse interval [p + 1 - 2 * sqrt(p), p + 1 + 2 * sqrt(p)].
+    /// See "An Introduction to Mathematical Cryptography", section 6.4.
+    fn count_points_bsgs(&self) -> u64 {
+        let width = isqrt(4 * self.p);
+        let (low, high) = (self.p + 1 - width, self.p + 1 + width);
+        let mut exponent = 1;
+        for point in self.points().take(10) {
+            exponent = lcm(exponent, self.order(point, low, high));
+            let first_multiple = low.div_ceil(exponent) * exponent;
+            if first_multiple + exponent > high {
+                return first_multiple;
+            }
+        }
+        self.count_points_naive()
+    }
+
+    /// Returns the order of `point`, through a multiple of it in [low, high]
+    /// found with the baby-step giant-step method.
+    fn order(&self, point: Point, low: u64, high: u64) -> u64 {
+        let steps = isqrt(high - low) + 1;
+        // [j]P for j in [0, steps)
+        let mut baby_steps = HashMap::new();
+        let mut multiple = None;
+        for j in 0..steps {
+            baby_steps.entry(multiple).or_insert(j);
+            multiple = self.add(multiple, point);
+        }
+        // [low + i * steps]P = -[j]P
+        let giant_step = self.multiply(point, steps);
+        let mut multiple = self.multiply(point, low);
+        let mut i = 0;
+        let mut order = loop {
+            if let Some(j) = baby_steps.get(&self.negate(multiple)) {
+                break low + i * steps + j;
+            }
+            multiple = self.add(multiple, giant_step);
+            i += 1;
+        };
+        for factor in prime_factors(order) {
+            while order % factor == 0 && self.multiply(point, order / factor).is_none() {
+                order /= factor;
+            }
+        }
+        order
+    }
+
+    fn negate(&self, point: Point) -> Point {
+        point.map(|(x, y)| (x, (self.p - y) % self.p))
+    }
+
+    fn add(&self, left: Point, right: Point) -> Point {
+        let p = self.p;
+        let ((x1, y1), (x2, y2)) = match (left, right) {
+            (None, point) | (point, None) => return point,
+            (Some(left), Some(right)) => (left, right),
+        };
+        let slope = if x1 != x2 {
+            mul_mod((y2 + p - y1) % p, inv_mod((x2 + p - x1) % p, p), p)
+        } else if y1 == y2 && y1 != 0 {
+            let numerator = (3 * mul_mod(x1, x1, p) + self.a) % p;
+            mul_mod(numerator, inv_mod(2 * y1 % p, p), p)
+        } else {
+            return None;
+        };
+        let x3 = (mul_mod(slope, slope, p) + 2 * p - x1 - x2) % p;
+        let y3 = (mul_mod(slope, (x1 + p - x3) % p, p) + p - y1) % p;
+        Some((x3, y3))
+    }
+
+    fn multiply(&self, point: Point, mut times: u64) -> Point {
+        let mut result = None;
+        let mut base = point;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = self.add(result, base);
+            }
+            times >>= 1;
+            base = self.add(base, base);
+        }
+        result
+    }
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (a as u128 * b as u128 % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, p: u64) -> u64 {
+    let mut result = 1 % p;
+    base %= p;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+        exponent >>= 1;
+        base = mul_mod(base, base, p);
+    }
+    result
+}
+
+fn inv_mod(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+/// Returns 0 for zero, 1 for non-zero squares and -1 otherwise.
+fn legendre(a: u64, p: u64) -> i8 {
+    match pow_mod(a, (p - 1) / 2, p) {
+        0 => 0,
+        1 => 1,
+        _ => -1,
+    }
+}
+
+/// Returns the smallest square root of `a` modulo `p`, using Tonelli-Shanks.
+fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    match legendre(a, p) {
+        0 => return Some(0),
+        -1 => return None,
+        _ => {}
+    }
+    // p - 1 = q * 2^s with q odd
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|z| legendre(*z, p) == -1)?;
+    let mut m = s;
+    let mut c = pow_mod(z, q, p);
+    let mut t = pow_mod(a, q, p);
+    let mut root = pow_mod(a, q.div_ceil(2), p);
+    while t != 1 {
+        // Smallest i such that t^(2^i) = 1
+        let i = (1..m).find(|i| pow_mod(t, 1 << i, p) == 1)?;
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        root = mul_mod(root, b, p);
+    }
+    Some(root.min(p - root))
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// Returns the distinct prime factors of `n`, found by trial division.
+fn prime_factors(mut n: u64) -> Vec<u64> {
+    let mut factors = vec![];
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            factors.push(d);
+            while n.is_multiple_of(d) {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+fn largest_prime_factor(n: u64) -> u64 {
+    prime_factors(n).pop().unwrap_or(1)
+}
+
+/// Returns the smallest `k` such that `a^k = 1` modulo `n`, if any.
+fn multiplicative_order(a: u64, n: u64) -> Option<u32> {
+    let mut power = a % n;
+    for k in 1..n.min(u32::MAX as u64) as u32 {
+        if power == 1 % n {
+            return Some(k);
+        }
+        power = mul_mod(power, a, n);
+    }
+    None
+}
+
+fn isqrt(n: u64) -> u64 {
+    let mut root = (n as f64).sqrt() as u64;
+    while root * root > n {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+    root
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supersingular_parameters(subgroup_bits: RangeInclusive<u32>) -> SearchParameters {
+        SearchParameters {
+            family: CurveFamily::Supersingular,
+            embedding_degree: 2,
+            subgroup_bits,
+            max_field_bits: 7,
+            max_cofactor: 12,
+            point_counting: PointCounting::BabyStepGiantStep,
+            max_results: 10,
+        }
+    }
+
+    #[test]
+    fn baby_step_giant_step_matches_naive_counting() {
+        for p in [5, 7, 11, 13, 59, 101, 103, 1009] {
+            for (a, b) in [(1, 0), (0, 1), (2, 3), (5, 7)] {
+                let curve = Curve { p, a, b };
+                if curve.is_singular() {
+                    continue;
+                }
+                assert_eq!(
+                    curve.count_points_bsgs(),
+                    curve.count_points_naive(),
+                    "y^2 = x^3 + {a} * x + {b} over F_{p}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn curve_59_has_60_points() {
+        let curve = Curve { p: 59, a: 1, b: 0 };
+        assert_eq!(curve.count_points(PointCounting::Naive), 60);
+        assert_eq!(curve.count_points(PointCounting::BabyStepGiantStep), 60);
+    }
+
+    #[test]
+    fn square_roots_modulo_primes() {
+        for p in [7, 13, 17, 41, 59] {
+            for a in 0..p {
+                match sqrt_mod(a, p) {
+                    Some(root) => assert_eq!(mul_mod(root, root, p), a),
+                    None => assert_eq!(legendre(a, p), -1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn supersingular_search_finds_the_predefined_curves() {
+        let curves = search(&supersingular_parameters(4..=4)).unwrap();
+        let primes: Vec<_> = curves.iter().map(|curve| (curve.p, curve.r)).collect();
+        assert!(primes.contains(&(43, 11)));
+        assert!(primes.contains(&(103, 13)));
+        let curves = search(&supersingular_parameters(3..=3)).unwrap();
+        let curve_59 = curves.iter().find(|curve| curve.p == 59).unwrap();
+        assert_eq!(
+            (curve_59.number_of_points, curve_59.r, curve_59.cofactor),
+            (60, 5, 12)
+        );
+    }
+
+    #[test]
+    fn generators_have_order_r() {
+        let mut parameters = supersingular_parameters(3..=5);
+        for family in [CurveFamily::Supersingular, CurveFamily::Ordinary] {
+            parameters.family = family;
+            parameters.embedding_degree = if family == CurveFamily::Ordinary {
+                3
+            } else {
+                2
+            };
+            parameters.max_cofactor = 4;
+            let curves = search(&parameters).unwrap();
+            assert!(!curves.is_empty(), "{family:?}");
+            for found in curves {
+                let curve = Curve {
+                    p: found.p,
+                    a: found.a,
+                    b: found.b,
+                };
+                let generator = Some(found.generator);
+                assert_eq!(
+                    curve.rhs(found.generator.0),
+                    mul_mod(found.generator.1, found.generator.1, found.p)
+                );
+                assert_eq!(curve.multiply(generator, found.r), None);
+                assert_eq!(found.number_of_points, found.r * found.cofactor);
+                assert_eq!(pow_mod(found.p, found.embedding_degree as u64, found.r), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn ordinary_curves_are_not_supersingular() {
+        let parameters = SearchParameters {
+            family: CurveFamily::Ordinary,
+            embedding_degree: 4,
+            subgroup_bits: 3..=6,
+            max_field_bits: 6,
+            max_cofactor: 2,
+            point_counting: PointCounting::Naive,
+            max_results: 5,
+        };
+        let curves = search(&parameters).unwrap();
+        assert!(!curves.is_empty());
+        for curve in curves {
+            assert_ne!(curve.number_of_points % curve.p, 1);
+            assert_eq!(curve.embedding_degree, 4);
+            assert_eq!(curve.to_curve_params(), None);
+        }
+    }
+
+    #[test]
+    fn supersingular_curves_need_embedding_degree_2() {
+        let mut parameters = supersingular_parameters(3..=4);
+        parameters.embedding_degree = 3;
+        assert_eq!(
+            search(&parameters),
+            Err(SearchError::UnsupportedEmbeddingDegree)
+        );
+    }
+
+    #[test]
+    fn emitted_parameters_of_curve_59() {
+        let curves = search(&supersingular_parameters(3..=3)).unwrap();
+        let curve_59 = curves.iter().find(|curve| curve.p == 59).unwrap();
+        let code = curve_59.to_curve_params().unwrap();
+        assert!(code.contains("pub struct Curve59;"));
+        assert!(code.contains("type ScalarField = FieldElement<5>;"));
+        assert!(code.contains("const COFACTOR: u128 = 12;"));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::math::elliptic_curve::CurveParams;
+use crate::math::field::Field;
+use crate::math::field_element::FieldElement;
+use crate::math::field_extension_element::FieldExtensionElement;
+
+// All the curves in this file have the form y^2 = x^3 + x over a prime field
+// of order p = 3 mod 4. They are supersingular, so they have p + 1 points
+// and embedding degree 2. The map (x, y) -> (-x, i * y), where i^2 = -1,
+// is a distortion map for them.
+
+/// Distortion map shared by every curve y^2 = x^3 + x with p = 3 mod 4.
+fn distortion_map<F: Field>(
+    [x, y, z]: &[FieldExtensionElement<F>; 3],
+) -> [FieldExtensionElement<F>; 3] {
+    let i = FieldExtensionElement::from_coefficients([F::zero(), F::one()]);
+    [-x, y * i, z.clone()]
+}
+
+/// The elliptic curve taken from the book "Pairing for beginners", page 57.
+/// y^2 = x^3 + x over F_59, with a subgroup of order 5.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve59;
+
+impl CurveParams for Curve59 {
+    type BaseField = FieldElement<59>;
+    type ScalarField = FieldElement<5>;
+
+    const ORDER_P: u128 = 59;
+    const ORDER_R: u128 = 5;
+    const COFACTOR: u128 = 12;
+    const EMBEDDING_DEGREE: u32 = 2;
+
+    fn a() -> Self::BaseField {
+        FieldElement::new(1)
+    }
+
+    fn b() -> Self::BaseField {
+        FieldElement::new(0)
+    }
+
+    fn generator_affine() -> (Self::BaseField, Self::BaseField) {
+        (FieldElement::new(35), FieldElement::new(31))
+    }
+
+    fn distortion_map(
+        coordinates: &[FieldExtensionElement<Self::BaseField>; 3],
+    ) -> [FieldExtensionElement<Self::BaseField>; 3] {
+        distortion_map(coordinates)
+    }
+
+    /// (p + 1) / r = 12 = 3 * 2^2
+    fn final_exponentiation_hard_part(
+        f: &FieldExtensionElement<Self::BaseField>,
+    ) -> FieldExtensionElement<Self::BaseField> {
+        let f3 = f.cyclotomic_square() * f;
+        f3.cyclotomic_square().cyclotomic_square()
+    }
+}
+
+/// y^2 = x^3 + x over F_43, with a subgroup of order 11.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve43;
+
+impl CurveParams for Curve43 {
+    type BaseField = FieldElement<43>;
+    type ScalarField = FieldElement<11>;
+
+    const ORDER_P: u128 = 43;
+    const ORDER_R: u128 = 11;
+    const COFACTOR: u128 = 4;
+    const EMBEDDING_DEGREE: u32 = 2;
+
+    fn a() -> Self::BaseField {
+        FieldElement::new(1)
+    }
+
+    fn b() -> Self::BaseField {
+        FieldElement::new(0)
+    }
+
+    fn generator_affine() -> (Self::BaseField, Self::BaseField) {
+        (FieldElement::new(31), FieldElement::new(18))
+    }
+
+    fn distortion_map(
+        coordinates: &[FieldExtensionElement<Self::BaseField>; 3],
+    ) -> [FieldExtensionElement<Self::BaseField>; 3] {
+        distortion_map(coordinates)
+    }
+
+    /// (p + 1) / r = 4 = 2^2
+    fn final_exponentiation_hard_part(
+        f: &FieldExtensionElement<Self::BaseField>,
+    ) -> FieldExtensionElement<Self::BaseField> {
+        f.cyclotomic_square().cyclotomic_square()
+    }
+}
+
+/// y^2 = x^3 + x over F_103, with a subgroup of order 13.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve103;
+
+impl CurveParams for Curve103 {
+    type BaseField = FieldElement<103>;
+    type ScalarField = FieldElement<13>;
+
+    const ORDER_P: u128 = 103;
+    const ORDER_R: u128 = 13;
+    const COFACTOR: u128 = 8;
+    const EMBEDDING_DEGREE: u32 = 2;
+
+    fn a() -> Self::BaseField {
+        FieldElement::new(1)
+    }
+
+    fn b() -> Self::BaseField {
+        FieldElement::new(0)
+    }
+
+    fn generator_affine() -> (Self::BaseField, Self::BaseField) {
+        (FieldElement::new(49), FieldElement::new(81))
+    }
+
+    fn distortion_map(
+        coordinates: &[FieldExtensionElement<Self::BaseField>; 3],
+    ) -> [FieldExtensionElement<Self::BaseField>; 3] {
+        distortion_map(coordinates)
+    }
+
+    /// (p + 1) / r = 8 = 2^3
+    fn final_exponentiation_hard_part(
+        f: &FieldExtensionElement<Self::BaseField>,
+    ) -> FieldExtensionElement<Self::BaseField> {
+        f.cyclotomic_square()
+            .cyclotomic_square()
+            .cyclotomic_square()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::cyclic_group::{CyclicGroup, Group, PairingEngine};
+    use crate::math::elliptic_curve::EllipticCurveElement;
+
+    fn generator_has_order_r<C: CurveParams>() {
+        let g = EllipticCurveElement::<C>::generator();
+        assert_ne!(g, EllipticCurveElement::neutral_element());
+        assert_eq!(
+            g.operate_with_self(C::ORDER_R),
+            EllipticCurveElement::neutral_element()
+        );
+    }
+
+    fn pairing_is_bilinear_and_non_degenerate<C: CurveParams>() {
+        let g = EllipticCurveElement::<C>::generator();
+        let e = EllipticCurveElement::pairing(&g, &g);
+        assert_ne!(e, FieldExtensionElement::one());
+        assert_eq!(
+            EllipticCurveElement::pairing(&g.operate_with_self(2u64), &g.operate_with_self(3u64)),
+            e.pow(6)
+        );
+    }
+
+    #[test]
+    fn curve_59_generator_has_order_5() {
+        generator_has_order_r::<Curve59>();
+    }
+
+    #[test]
+    fn curve_43_generator_has_order_11() {
+        generator_has_order_r::<Curve43>();
+    }
+
+    #[test]
+    fn curve_103_generator_has_order_13() {
+        generator_has_order_r::<Curve103>();
+    }
+
+    #[test]
+    fn curve_59_pairing_is_bilinear_and_non_degenerate() {
+        pairing_is_bilinear_and_non_degenerate::<Curve59>();
+    }
+
+    #[test]
+    fn curve_43_pairing_is_bilinear_and_non_degenerate() {
+        pairing_is_bilinear_and_non_degenerate::<Curve43>();
+    }
+
+    #[test]
+    fn curve_103_pairing_is_bilinear_and_non_degenerate() {
+        pairing_is_bilinear_and_non_degenerate::<Curve103>();
+    }
+
+    fn assert_hard_part_matches_its_power<C: CurveParams>() {
+        let f = FieldExtensionElement::<C::BaseField>::from_coefficients([
+            C::BaseField::from_u64(2),
+            C::BaseField::from_u64(7),
+        ]);
+        // f^(p - 1) has norm one.
+        let f = f.conjugate() / f;
+        assert_eq!(
+            C::final_exponentiation_hard_part(&f),
+            f.pow(C::HARD_PART_POWER)
+        );
+    }
+
+    #[test]
+    fn addition_chains_of_the_hard_part_match_its_power() {
+        assert_hard_part_matches_its_power::<Curve59>();
+        assert_hard_part_matches_its_power::<Curve43>();
+        assert_hard_part_matches_its_power::<Curve103>();
+    }
+}
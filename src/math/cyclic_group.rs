@@ -1,10 +1,8 @@
-use std::ops::Mul;
+use super::field::{Field, PrimeField};
+use super::unsigned_integer::IsUnsignedInteger;
+use std::fmt::Debug;
 
-pub trait CyclicBilinearGroup: Clone {
-    type PairingOutput: Mul<Output = Self::PairingOutput> + PartialEq + Eq;
-    /// Returns a generator of the group. Every element of the group
-    /// has to be of the form `operate_with_self(generator(), k)` for some `k`.
-    fn generator() -> Self;
+pub trait Group: Clone + Debug + PartialEq + Eq + Send + Sync {
     /// Returns the neutral element of the group. The equality
     /// `neutral_element().operate_with(g) == g` must hold
     /// for every group element `g`.
@@ -12,11 +10,92 @@ pub trait CyclicBilinearGroup: Clone {
     /// Applies the group operation `times` times with itself
     /// The operation can be addition or multiplication depending on
     /// the notation of the particular group.
-    fn operate_with_self(&self, times: u128) -> Self;
+    /// The default implementation uses the right-to-left double-and-add method.
+    fn operate_with_self<T: IsUnsignedInteger>(&self, mut times: T) -> Self {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let mut result = Self::neutral_element();
+        let mut base = self.clone();
+
+        while times > zero {
+            // times % 2 == 1
+            if times & one == one {
+                result = result.operate_with(&base);
+            }
+            // times = times / 2
+            times = times >> 1;
+            base = base.operate_with(&base);
+        }
+        result
+    }
     /// Applies the group operation between `self` and `other`.
-    /// Thperation can be addition or multiplication depending on
+    /// The operation can be addition or multiplication depending on
     /// the notation of the particular group.
     fn operate_with(&self, other: &Self) -> Self;
+    /// Returns the element `g` such that `self.operate_with(g)`
+    /// is the neutral element.
+    fn inverse(&self) -> Self;
+}
+
+pub trait CyclicGroup: Group {
+    /// Field of integers modulo the order of the group.
+    type Scalar: PrimeField;
+
+    /// Returns a generator of the group. Every element of the group
+    /// has to be of the form `operate_with_self(generator(), k)` for some `k`.
+    fn generator() -> Self;
+
+    /// Applies the group operation `scalar` times with itself, where `scalar`
+    /// is taken as its representative in the range [0, order).
+    fn operate_with_scalar(&self, scalar: &Self::Scalar) -> Self {
+        self.operate_with_self(scalar.representative())
+    }
+
+    /// Returns `true` if `self` lies in the subgroup of order r, the order of
+    /// the scalar field, that is, if r * self is the neutral element.
+    /// Groups of order exactly r can override it to return `true`.
+    fn is_in_subgroup(&self) -> bool {
+        // (r - 1) * self + self
+        self.operate_with_scalar(&-Self::Scalar::one())
+            .operate_with(self)
+            == Self::neutral_element()
+    }
+}
+
+/// A bilinear map e: G1 x G2 -> GT between cyclic groups of the same order.
+/// Symmetric pairings are the case where G1 and G2 are the same group.
+pub trait PairingEngine: Debug + Clone + PartialEq + Eq {
+    /// Field of integers modulo the common order of G1 and G2.
+    type Scalar: PrimeField;
+    type G1: CyclicGroup<Scalar = Self::Scalar>;
+    type G2: CyclicGroup<Scalar = Self::Scalar>;
+    type GT: Group;
+    /// A point of G1 with the data that the pairing computes from it
+    /// alone precomputed, like the lines of Miller's algorithm.
+    type G1Prepared: Clone + Debug + PartialEq + Eq;
+    /// A point of G2 with the data that the pairing computes from it
+    /// alone precomputed, like the lines of Miller's algorithm.
+    type G2Prepared: Clone + Debug + PartialEq + Eq;
+
     /// A bilinear map.
-    fn pairing(&self, other: &Self) -> Self::PairingOutput;
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::GT;
+
+    fn prepare_g1(p: &Self::G1) -> Self::G1Prepared;
+
+    fn prepare_g2(q: &Self::G2) -> Self::G2Prepared;
+
+    /// Computes the product of the pairings of every pair of prepared points.
+    /// Useful when some arguments are fixed, since they are prepared only once.
+    fn multi_pairing_prepared(pairs: &[(&Self::G1Prepared, &Self::G2Prepared)]) -> Self::GT;
+
+    /// Computes the product of the pairings of every pair `(p, q)`.
+    /// Engines can override it to share work among the pairings,
+    /// like the final exponentiation.
+    fn multi_pairing(pairs: &[(Self::G1, Self::G2)]) -> Self::GT {
+        pairs
+            .iter()
+            .fold(Self::GT::neutral_element(), |product, (p, q)| {
+                product.operate_with(&Self::pairing(p, q))
+            })
+    }
 }
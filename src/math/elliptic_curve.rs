@@ -1,137 +1,182 @@
-use super::super::config::{
-    ELLIPTIC_CURVE_A, ELLIPTIC_CURVE_B, GENERATOR_AFFINE_X, GENERATOR_AFFINE_Y, ORDER_P, ORDER_R,
-    TARGET_NORMALIZATION_POWER,
-};
 use super::{
-    cyclic_group::CyclicBilinearGroup, field_element::FieldElement,
-    field_extension_element::FieldExtensionElement, polynomial::Polynomial,
+    cyclic_group::{Group, PairingEngine},
+    field::{Field, PrimeField},
+    field_extension_element::FieldExtensionElement,
+    short_weierstrass::{ShortWeierstrassParams, ShortWeierstrassPoint},
 };
-use std::ops;
+use std::fmt::Debug;
+use std::marker::PhantomData;
 
-type FE = FieldElement<ORDER_P>;
 #[allow(clippy::upper_case_acronyms)]
-type FEE = FieldExtensionElement;
-
-/// Represents an elliptic curve point using the projective short Weierstrass form:
-///   y^2 * z = x^3 + a * x * z^2 + b * z^3
-/// x, y and z variables are field extension elements.
-#[derive(Debug, Clone)]
-pub struct EllipticCurveElement {
-    x: FEE,
-    y: FEE,
-    z: FEE,
+type FEE<C> = FieldExtensionElement<<C as CurveParams>::BaseField>;
+
+/// Parameters of a pairing friendly elliptic curve in short Weierstrass form:
+///   y^2 = x^3 + a * x + b
+/// The points of the curve have coordinates in the quadratic extension
+/// of the base field, so the embedding degree is expected to be 2.
+pub trait CurveParams: Debug + Clone + PartialEq + Eq {
+    /// Field where the coefficients and the generator coordinates live.
+    type BaseField: PrimeField;
+    /// Field of the scalars, its order is the order of the generator.
+    type ScalarField: PrimeField;
+
+    /// Order of the base field.
+    const ORDER_P: u128;
+    /// Order of the subgroup generated by the generator.
+    const ORDER_R: u128;
+    /// Number of points of the curve over the base field divided by `ORDER_R`.
+    const COFACTOR: u128;
+    /// Smallest `k` such that `ORDER_R` divides `ORDER_P^k - 1`.
+    const EMBEDDING_DEGREE: u32;
+    /// Power used to map the output of Miller's algorithm to the `ORDER_R`-th roots of unity.
+    const TARGET_NORMALIZATION_POWER: u128 =
+        (Self::ORDER_P.pow(Self::EMBEDDING_DEGREE) - 1) / Self::ORDER_R;
+    /// The factor (p + 1) / r of `TARGET_NORMALIZATION_POWER` = (p - 1) * ((p + 1) / r).
+    /// Since the embedding degree is 2, r does not divide p - 1, so it divides p + 1.
+    const HARD_PART_POWER: u128 = (Self::ORDER_P + 1) / Self::ORDER_R;
+    /// Loop count of the ate pairing, t - 1 reduced modulo r, where t is the trace of
+    /// Frobenius. Since r divides the number of points p + 1 - t, it is p modulo r.
+    const ATE_LOOP_COUNT: u128 = Self::ORDER_P % Self::ORDER_R;
+
+    /// Returns the coefficient `a` of the defining equation.
+    fn a() -> Self::BaseField;
+    /// Returns the coefficient `b` of the defining equation.
+    fn b() -> Self::BaseField;
+    /// Returns the affine coordinates (x, y) of the generator.
+    fn generator_affine() -> (Self::BaseField, Self::BaseField);
+    /// Maps the Jacobian coordinates [x, y, z] of a point to the coordinates
+    /// of a point linearly independent from it.
+    fn distortion_map(
+        coordinates: &[FieldExtensionElement<Self::BaseField>; 3],
+    ) -> [FieldExtensionElement<Self::BaseField>; 3];
+
+    /// Raises `f`, of norm one, to `HARD_PART_POWER`.
+    /// Curves can override it with an addition chain for their power.
+    fn final_exponentiation_hard_part(
+        f: &FieldExtensionElement<Self::BaseField>,
+    ) -> FieldExtensionElement<Self::BaseField> {
+        f.cyclotomic_pow(Self::HARD_PART_POWER)
+    }
 }
 
-impl EllipticCurveElement {
-    /// Creates an elliptic curve point giving the (x, y, z) coordinates.
-    fn new(x: FEE, y: FEE, z: FEE) -> Self {
-        assert_eq!(
-            Self::defining_equation(&x, &y, &z),
-            FEE::new_base(0),
-            "Point ({:?}, {:?}, {:?}) does not belong to the elliptic curve.",
-            x,
-            y,
-            z
-        );
-        Self { x, y, z }
+#[derive(Debug, PartialEq, Eq)]
+pub enum EllipticCurveError {
+    /// The coordinates do not satisfy the equation of the curve.
+    InvalidPoint,
+    /// The bytes are not the canonical encoding of any point.
+    InvalidEncoding,
+}
+
+/// The curve given by `C` with its points taken over the quadratic extension
+/// of the base field, which also holds the images of the distortion map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionCurve<C: CurveParams>(PhantomData<C>);
+
+impl<C: CurveParams> ShortWeierstrassParams for ExtensionCurve<C> {
+    type BaseField = FEE<C>;
+    type ScalarField = C::ScalarField;
+    type Cofactor = u128;
+
+    /// The cofactor of the curve over the base field.
+    const COFACTOR: u128 = C::COFACTOR;
+
+    fn a() -> FEE<C> {
+        FEE::<C>::new_base(C::a())
     }
 
-    /// Evaluates the short Weierstrass equation at (x, y z).
-    /// Useful for checking if (x, y, z) belongs to the elliptic curve.
-    fn defining_equation(x: &FEE, y: &FEE, z: &FEE) -> FEE {
-        y.pow(2) * z
-            - x.pow(3)
-            - FEE::new_base(ELLIPTIC_CURVE_A) * x * z.pow(2)
-            - FEE::new_base(ELLIPTIC_CURVE_B) * z.pow(3)
+    fn b() -> FEE<C> {
+        FEE::<C>::new_base(C::b())
     }
 
-    /// Normalize the projective coordinates to obtain affine coordinates
-    /// of the form (x, y, 1)
-    /// Panics if `self` is the point at infinity
-    fn affine(&self) -> Self {
-        assert!(
-            self != &EllipticCurveElement::neutral_element(),
-            "The point at infinity is not affine."
-        );
-        Self::new(&self.x / &self.z, &self.y / &self.z, FEE::new_base(1))
+    fn generator_affine() -> (FEE<C>, FEE<C>) {
+        let (x, y) = C::generator_affine();
+        (FEE::<C>::new_base(x), FEE::<C>::new_base(y))
     }
 
-    /// Evaluates the line between points `self` and `r` at point `q`
-    fn line(&self, r: &Self, q: &Self) -> FEE {
-        assert_ne!(
-            *q,
-            Self::neutral_element(),
-            "q cannot be the point at infinity."
+    /// Over the extension the points of order r do not form a cyclic group, so
+    /// `COFACTOR` alone would not reach the subgroup of the generator. The trace
+    /// P + Frobenius(P) first maps the point to the curve over the base field,
+    /// where the conjugate of each coordinate is its p-th power.
+    fn clear_cofactor(point: &EllipticCurveElement<C>) -> EllipticCurveElement<C> {
+        let frobenius = EllipticCurveElement::<C>::new_unchecked(
+            point.x().conjugate(),
+            point.y().conjugate(),
+            point.z().conjugate(),
         );
+        point
+            .operate_with(&frobenius)
+            .operate_with_self(C::COFACTOR)
+    }
+}
+
+/// A point of a pairing friendly curve, with coordinates in the quadratic extension.
+/// The group law, the encodings and the sampling of points are the ones of
+/// `ShortWeierstrassPoint`; this module adds the symmetric pairing.
+pub type EllipticCurveElement<C> = ShortWeierstrassPoint<ExtensionCurve<C>>;
+
+impl<C: CurveParams> EllipticCurveElement<C> {
+    /// Pairings take values in the extension where the coordinates live, so its
+    /// degree has to be the embedding degree of the curve. Checked at compile time.
+    const EMBEDDING_DEGREE_IS_THE_EXTENSION_DEGREE: () = assert!(
+        C::EMBEDDING_DEGREE as usize == FEE::<C>::EXTENSION_DEGREE,
+        "The embedding degree does not match the degree of the coordinates field."
+    );
+
+    /// Returns the line between points `self` and `r`, which have to be affine
+    /// unless they are the point at infinity.
+    fn line(&self, r: &Self) -> Line<C> {
         if *self == Self::neutral_element() || *r == Self::neutral_element() {
             if self == r {
-                return FEE::new_base(1);
+                return Line::One;
             }
             if *self == Self::neutral_element() {
-                &q.x - &r.x
+                Line::Vertical(r.x().clone())
             } else {
-                &q.x - &self.x
+                Line::Vertical(self.x().clone())
             }
         } else if self != r {
-            if self.x == r.x {
-                return &q.x - &self.x;
+            if self.x() == r.x() {
+                Line::Vertical(self.x().clone())
             } else {
-                let l = (&r.y - &self.y) / (&r.x - &self.x);
-                return &q.y - &self.y - l * (&q.x - &self.x);
+                let slope = (r.y() - self.y()) / (r.x() - self.x());
+                Line::Sloped {
+                    x: self.x().clone(),
+                    y: self.y().clone(),
+                    slope,
+                }
             }
         } else {
-            let numerator = FEE::new_base(3) * &self.x.pow(2) + FEE::new_base(ELLIPTIC_CURVE_A);
-            let denominator = FEE::new_base(2) * &self.y;
-            if denominator == FEE::new_base(0) {
-                return &q.x - &self.x;
+            let numerator = FEE::<C>::from_u64(3) * &self.x().pow(2) + FEE::<C>::new_base(C::a());
+            let denominator = FEE::<C>::from_u64(2) * self.y();
+            if denominator == FEE::<C>::zero() {
+                Line::Vertical(self.x().clone())
             } else {
-                let l = numerator / denominator;
-                return &q.y - &self.y - l * (&q.x - &self.x);
+                Line::Sloped {
+                    x: self.x().clone(),
+                    y: self.y().clone(),
+                    slope: numerator / denominator,
+                }
             }
         }
     }
 
     /// Computes Miller's algorithm between points `p` and `q`.
-    /// The implementaiton is based on Sagemath's sourcecode:
-    /// See `_miller_` method on page 114
-    /// https://www.sagemath.org/files/thesis/hansen-thesis-2009.pdf
-    /// Other resources can be found at "Pairings for beginners" from Craig Costello, Algorithm 5.1, page 79.
-    fn miller(p: &Self, q: &Self) -> FEE {
-        let p = p.affine();
-        let q = q.affine();
-        let mut order_r = ORDER_R;
-        let mut bs = vec![];
-        while order_r > 0 {
-            bs.insert(0, order_r & 1);
-            order_r >>= 1;
-        }
-
-        let mut f = FEE::new_base(1);
-        let mut r = p.clone();
-
-        for b in bs[1..].iter() {
-            let s = r.operate_with(&r).affine();
-            f = f.pow(2) * (r.line(&r, &q) / s.line(&-(&s), &q));
-            r = s;
+    /// See `PreparedPoint::miller`.
+    pub(crate) fn miller(p: &Self, q: &Self) -> FEE<C> {
+        Self::miller_with_loop_count(p, q, C::ORDER_R)
+    }
 
-            if *b == 1 {
-                let mut s = r.operate_with(&p);
-                if s != Self::neutral_element() {
-                    s = s.affine();
-                }
-                f = f * (r.line(&p, &q) / s.line(&-(&s), &q));
-                r = s;
-            }
-        }
-        f
+    /// Computes Miller's algorithm between points `p` and `q`,
+    /// over the bits of `loop_count` instead of the order r.
+    pub(crate) fn miller_with_loop_count(p: &Self, q: &Self, loop_count: u128) -> FEE<C> {
+        PreparedPoint::with_loop_count(p, loop_count).miller(&q.normalize())
     }
 
     /// Computes the Weil pairing between points `p` and `q`.
     /// See "Pairing for beginners" from Craig Costello, page 79.
-    #[allow(unused)]
-    fn weil_pairing(p: &Self, q: &Self) -> FEE {
+    pub(crate) fn weil_pairing(p: &Self, q: &Self) -> FEE<C> {
         if *p == Self::neutral_element() || *q == Self::neutral_element() || p == q {
-            FEE::new_base(1)
+            FEE::<C>::one()
         } else {
             let numerator = Self::miller(p, q);
             let denominator = Self::miller(q, p);
@@ -142,173 +187,226 @@ impl EllipticCurveElement {
 
     /// Computes the Tate pairing between points `p` and `q`.
     /// See "Pairing for beginners" from Craig Costello, page 79.
-    fn tate_pairing(p: &Self, q: &Self) -> FEE {
+    fn tate_pairing(p: &Self, q: &Self) -> FEE<C> {
         if *p == Self::neutral_element() || *q == Self::neutral_element() || p == q {
-            FEE::new_base(1)
+            FEE::<C>::one()
         } else {
-            Self::miller(p, q).pow(TARGET_NORMALIZATION_POWER)
+            Self::final_exponentiation(&Self::miller(p, q))
         }
     }
 
+    /// Raises `f` to `TARGET_NORMALIZATION_POWER`, split as (p - 1) * ((p + 1) / r).
+    /// The p power of `f` is its conjugate, so the easy part f^(p - 1) = conj(f) / f
+    /// takes a single inversion. Its result has norm one, which makes
+    /// squarings cheaper in the hard part.
+    pub(crate) fn final_exponentiation(f: &FEE<C>) -> FEE<C> {
+        let f = f.conjugate() * f.clone().inv();
+        C::final_exponentiation_hard_part(&f)
+    }
+
     /// Apply a distorsion map to point `p`.
     /// This is useful for converting points living in the base field
     /// to points living in the extension field.
-    /// The current implementation only works for the elliptic curve with A=1 and B=0
-    /// ORDER_P=59. This curve was chosen because it is supersingular.
-    fn distorsion_map(p: &Self) -> Self {
-        let t = FEE::new(Polynomial::new_monomial(FE::new(1), 1));
-        Self::new(-&p.x, &p.y * t, p.z.clone())
+    pub(crate) fn distorsion_map(p: &Self) -> Self {
+        let [x, y, z] = C::distortion_map(&[p.x().clone(), p.y().clone(), p.z().clone()]);
+        Self::new_unchecked(x, y, z)
     }
 }
 
-impl PartialEq for EllipticCurveElement {
-    fn eq(&self, other: &Self) -> bool {
-        // Projective equality relation: first point has to be a multiple of the other
-        (&self.x * &other.z == &self.z * &other.x) && (&self.x * &other.y == &self.y * &other.x)
-    }
+/// A line of Miller's algorithm, kept apart from the point where it is evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line<C: CurveParams> {
+    /// The constant function one.
+    One,
+    /// The vertical line through the points with the given x coordinate.
+    Vertical(FEE<C>),
+    /// The line through (x, y) with the given slope.
+    Sloped { x: FEE<C>, y: FEE<C>, slope: FEE<C> },
 }
-impl Eq for EllipticCurveElement {}
-
-impl ops::Neg for &EllipticCurveElement {
-    type Output = EllipticCurveElement;
 
-    fn neg(self) -> Self::Output {
-        Self::Output::new(self.x.clone(), -self.y.clone(), self.z.clone())
+impl<C: CurveParams> Line<C> {
+    /// Evaluates the line at the affine point `q`.
+    fn evaluate(&self, q: &EllipticCurveElement<C>) -> FEE<C> {
+        assert_ne!(
+            *q,
+            EllipticCurveElement::neutral_element(),
+            "q cannot be the point at infinity."
+        );
+        match self {
+            Line::One => FEE::<C>::one(),
+            Line::Vertical(x) => q.x() - x,
+            Line::Sloped { x, y, slope } => q.y() - y - slope * (q.x() - x),
+        }
     }
 }
 
-impl ops::Neg for EllipticCurveElement {
-    type Output = EllipticCurveElement;
-
-    fn neg(self) -> Self::Output {
-        -&self
-    }
+/// A point together with the lines that Miller's algorithm computes from it,
+/// so that they can be evaluated at many points without computing them again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedPoint<C: CurveParams> {
+    point: EllipticCurveElement<C>,
+    loop_count: u128,
+    /// Numerator and denominator lines of every step, in the order they are used.
+    lines: Vec<(Line<C>, Line<C>)>,
 }
 
-impl CyclicBilinearGroup for EllipticCurveElement {
-    type PairingOutput = FEE;
-
-    fn generator() -> Self {
-        Self::new(
-            FEE::new_base(GENERATOR_AFFINE_X),
-            FEE::new_base(GENERATOR_AFFINE_Y),
-            FEE::new_base(1),
-        )
-    }
-
-    fn neutral_element() -> Self {
-        Self::new(FEE::new_base(0), FEE::new_base(1), FEE::new_base(0))
+impl<C: CurveParams> PreparedPoint<C> {
+    /// Computes the lines of Miller's algorithm over `p`.
+    /// There are none when `p` is the point at infinity.
+    pub fn new(p: &EllipticCurveElement<C>) -> Self {
+        Self::with_loop_count(p, C::ORDER_R)
     }
 
-    fn operate_with_self(&self, times: u128) -> Self {
-        let mut times = times;
-        let mut result = Self::neutral_element();
-        let mut base = self.clone();
+    /// Computes the lines of Miller's algorithm over `p` and the bits of
+    /// `loop_count`, which has to be between 2 and r.
+    fn with_loop_count(p: &EllipticCurveElement<C>, loop_count: u128) -> Self {
+        if p.is_neutral_element() {
+            return Self {
+                point: p.clone(),
+                loop_count,
+                lines: vec![],
+            };
+        }
+        let p = p.normalize();
+        let mut lines = vec![];
+        let mut r = p.clone();
+        for b in loop_bits(loop_count) {
+            let s = r.operate_with(&r).normalize();
+            lines.push((r.line(&r), s.line(&-(&s))));
+            r = s;
 
-        while times > 0 {
-            // times % 2 == 1
-            if times & 1 == 1 {
-                result = result.operate_with(&base);
+            if b {
+                let mut s = r.operate_with(&p);
+                if s != EllipticCurveElement::neutral_element() {
+                    s = s.normalize();
+                }
+                lines.push((r.line(&p), s.line(&-(&s))));
+                r = s;
             }
-            // times = times / 2
-            times >>= 1;
-            base = base.operate_with(&base);
         }
-        result
+        Self {
+            point: p,
+            loop_count,
+            lines,
+        }
     }
 
-    /// Computes the addition of `self` and `other`.
-    /// Taken from Moonmath (Algorithm 7, page 89)
-    fn operate_with(&self, other: &Self) -> Self {
-        if *other == Self::neutral_element() {
-            self.clone()
-        } else if *self == Self::neutral_element() {
-            other.clone()
-        } else {
-            let u1 = &other.y * &self.z;
-            let u2 = &self.y * &other.z;
-            let v1 = &other.x * &self.z;
-            let v2 = &self.x * &other.z;
-            if v1 == v2 {
-                if u1 != u2 || self.y == FEE::new_base(0) {
-                    Self::neutral_element()
-                } else {
-                    let w = FEE::new_base(ELLIPTIC_CURVE_A) * self.z.pow(2)
-                        + FEE::new_base(3) * self.x.pow(2);
-                    let s = &self.y * &self.z;
-                    let b = &self.x * &self.y * &s;
-                    let h = w.pow(2) - FEE::new_base(8) * &b;
-                    let xp = FEE::new_base(2) * &h * &s;
-                    let yp = w * (FEE::new_base(4) * &b - &h)
-                        - FEE::new_base(8) * self.y.pow(2) * s.pow(2);
-                    let zp = FEE::new_base(8) * s.pow(3);
-                    Self::new(xp, yp, zp)
-                }
-            } else {
-                let u = u1 - &u2;
-                let v = v1 - &v2;
-                let w = &self.z * &other.z;
-                let a = u.pow(2) * &w - v.pow(3) - FEE::new_base(2) * v.pow(2) * &v2;
-                let xp = &v * &a;
-                let yp = u * (v.pow(2) * v2 - a) - v.pow(3) * u2;
-                let zp = v.pow(3) * w;
-                Self::new(xp, yp, zp)
+    /// Computes Miller's algorithm between the prepared point and the affine point `q`.
+    /// The implementaiton is based on Sagemath's sourcecode:
+    /// See `_miller_` method on page 114
+    /// https://www.sagemath.org/files/thesis/hansen-thesis-2009.pdf
+    /// Other resources can be found at "Pairings for beginners" from Craig Costello, Algorithm 5.1, page 79.
+    fn miller(&self, q: &EllipticCurveElement<C>) -> FEE<C> {
+        #[allow(clippy::let_unit_value)]
+        let () = EllipticCurveElement::<C>::EMBEDDING_DEGREE_IS_THE_EXTENSION_DEGREE;
+        assert!(
+            !self.point.is_neutral_element(),
+            "p cannot be the point at infinity."
+        );
+        // f = numerator / denominator, kept apart so that
+        // there is a single inversion at the end.
+        let mut numerator = FEE::<C>::one();
+        let mut denominator = FEE::<C>::one();
+        let mut lines = self.lines.iter();
+        let mut next_step = |numerator: FEE<C>, denominator: FEE<C>| {
+            let (n, d) = lines
+                .next()
+                .expect("There is a pair of lines for every step");
+            (numerator * n.evaluate(q), denominator * d.evaluate(q))
+        };
+
+        for b in loop_bits(self.loop_count) {
+            (numerator, denominator) = next_step(numerator.pow(2), denominator.pow(2));
+            if b {
+                (numerator, denominator) = next_step(numerator, denominator);
             }
         }
+        numerator / denominator
     }
+}
+
+/// Bits of `loop_count` after the leading one, from the most significant.
+fn loop_bits(loop_count: u128) -> impl Iterator<Item = bool> {
+    (0..127 - loop_count.leading_zeros())
+        .rev()
+        .map(move |i| (loop_count >> i) & 1 == 1)
+}
 
-    /// Computes a Type 1 Tate pairing between `self` and `other.
+impl<C: CurveParams> PairingEngine for EllipticCurveElement<C> {
+    type Scalar = C::ScalarField;
+    type G1 = Self;
+    type G2 = Self;
+    type GT = FEE<C>;
+    type G1Prepared = PreparedPoint<C>;
+    /// The affine image of the point under the distortion map.
+    type G2Prepared = Self;
+
+    /// Computes a Type 1 reduced Tate pairing between `p` and `q`.
     /// See "Pairing for beginners" from Craig Costello, section 4.2 Pairing types, page 58.
-    /// Note that a distorsion map is applied to `other` before using the Tate pairing.
+    /// Other variants are available in `math::pairing`.
+    /// Note that a distorsion map is applied to `q` before using the Tate pairing.
     /// So this method can be called with two field extension elements from the base field.
-    fn pairing(&self, other: &Self) -> Self::PairingOutput {
-        Self::tate_pairing(self, &Self::distorsion_map(other))
+    fn pairing(p: &Self, q: &Self) -> FEE<C> {
+        Self::tate_pairing(p, &Self::distorsion_map(q))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // This tests only apply for the specific curve found in the configuration file.
-    #[test]
-    fn create_valid_point_works() {
-        let point =
-            EllipticCurveElement::new(FEE::new_base(35), FEE::new_base(31), FEE::new_base(1));
-        assert_eq!(point.x, FEE::new_base(35));
-        assert_eq!(point.y, FEE::new_base(31));
-        assert_eq!(point.z, FEE::new_base(1));
+    /// Multiplies the outputs of Miller's algorithm and raises
+    /// the product to the final power only once.
+    fn multi_pairing(pairs: &[(Self, Self)]) -> FEE<C> {
+        let product = pairs
+            .iter()
+            .map(|(p, q)| (p, Self::distorsion_map(q)))
+            .filter(|(p, q)| !p.is_neutral_element() && !q.is_neutral_element() && *p != q)
+            .fold(FEE::<C>::one(), |product, (p, q)| {
+                product * Self::miller(p, &q)
+            });
+        Self::final_exponentiation(&product)
     }
 
-    #[test]
-    #[should_panic]
-    fn create_invalid_points_panicks() {
-        EllipticCurveElement::new(FEE::new_base(0), FEE::new_base(1), FEE::new_base(1));
+    fn prepare_g1(p: &Self) -> PreparedPoint<C> {
+        PreparedPoint::new(p)
     }
 
-    #[test]
-    fn operate_with_self_works() {
-        let mut point_1 = EllipticCurveElement::generator();
-        point_1 = point_1.operate_with_self(ORDER_R);
-        assert_eq!(point_1, EllipticCurveElement::neutral_element());
+    fn prepare_g2(q: &Self) -> Self {
+        let q = Self::distorsion_map(q);
+        if q.is_neutral_element() {
+            q
+        } else {
+            q.normalize()
+        }
     }
 
-    #[test]
-    fn doubling_a_point_works() {
-        let point =
-            EllipticCurveElement::new(FEE::new_base(35), FEE::new_base(31), FEE::new_base(1));
-        let expected_result =
-            EllipticCurveElement::new(FEE::new_base(25), FEE::new_base(29), FEE::new_base(1));
-        assert_eq!(point.operate_with_self(2).affine(), expected_result);
+    fn multi_pairing_prepared(pairs: &[(&PreparedPoint<C>, &Self)]) -> FEE<C> {
+        let product = pairs
+            .iter()
+            .filter(|(p, q)| {
+                !p.point.is_neutral_element() && !q.is_neutral_element() && p.point != **q
+            })
+            .fold(FEE::<C>::one(), |product, (p, q)| product * p.miller(q));
+        Self::final_exponentiation(&product)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::curves::supersingular::{Curve103, Curve43, Curve59};
+    use crate::math::cyclic_group::CyclicGroup;
+    use crate::math::field_element::FieldElement;
+    use crate::math::polynomial::Polynomial;
+
+    type FE = FieldElement<59>;
+    #[allow(clippy::upper_case_acronyms)]
+    type FEE = FieldExtensionElement<FE>;
+    type EllipticCurveElement = super::EllipticCurveElement<Curve59>;
 
     #[test]
     fn test_weil_pairing() {
-        let pa = EllipticCurveElement::new(FEE::new_base(35), FEE::new_base(31), FEE::new_base(1));
+        let pa = EllipticCurveElement::new(FEE::from_u64(35), FEE::from_u64(31), FEE::from_u64(1));
         let pb = EllipticCurveElement::new(
             FEE::new(Polynomial::new(vec![FE::new(24)])),
             FEE::new(Polynomial::new(vec![FE::new(0), FE::new(31)])),
-            FEE::new_base(1),
+            FEE::from_u64(1),
         );
         let expected_result = FEE::new(Polynomial::new(vec![FE::new(46), FE::new(3)]));
 
@@ -318,15 +416,123 @@ mod tests {
 
     #[test]
     fn test_tate_pairing() {
-        let pa = EllipticCurveElement::new(FEE::new_base(35), FEE::new_base(31), FEE::new_base(1));
+        let pa = EllipticCurveElement::new(FEE::from_u64(35), FEE::from_u64(31), FEE::from_u64(1));
         let pb = EllipticCurveElement::new(
             FEE::new(Polynomial::new(vec![FE::new(24)])),
             FEE::new(Polynomial::new(vec![FE::new(0), FE::new(31)])),
-            FEE::new_base(1),
+            FEE::from_u64(1),
         );
         let expected_result = FEE::new(Polynomial::new(vec![FE::new(42), FE::new(19)]));
 
         let result_weil = EllipticCurveElement::tate_pairing(&pa, &pb);
         assert_eq!(result_weil, expected_result);
     }
+
+    #[test]
+    fn multi_pairing_is_the_product_of_the_pairings() {
+        let g = EllipticCurveElement::generator();
+        let pairs = [
+            (g.operate_with_self(2u64), g.operate_with_self(3u64)),
+            (g.clone(), g.operate_with_self(4u64)),
+            (EllipticCurveElement::neutral_element(), g.clone()),
+        ];
+        let expected = pairs.iter().fold(FEE::one(), |product, (p, q)| {
+            product * EllipticCurveElement::pairing(p, q)
+        });
+        assert_eq!(EllipticCurveElement::multi_pairing(&pairs), expected);
+        assert_eq!(
+            EllipticCurveElement::multi_pairing(&[(g.clone(), g.clone()), (g.inverse(), g)]),
+            FEE::one()
+        );
+    }
+
+    #[test]
+    fn multi_pairing_prepared_matches_multi_pairing() {
+        let g = EllipticCurveElement::generator();
+        let pairs = [
+            (g.operate_with_self(2u64), g.operate_with_self(3u64)),
+            (g.clone(), g.operate_with_self(4u64)),
+            (EllipticCurveElement::neutral_element(), g.clone()),
+        ];
+        let prepared: Vec<_> = pairs
+            .iter()
+            .map(|(p, q)| {
+                (
+                    EllipticCurveElement::prepare_g1(p),
+                    EllipticCurveElement::prepare_g2(q),
+                )
+            })
+            .collect();
+        let prepared_pairs: Vec<_> = prepared.iter().map(|(p, q)| (p, q)).collect();
+        assert_eq!(
+            EllipticCurveElement::multi_pairing_prepared(&prepared_pairs),
+            EllipticCurveElement::multi_pairing(&pairs)
+        );
+    }
+
+    fn assert_final_exponentiation_matches_the_naive_one<C: CurveParams>() {
+        for i in 1..20 {
+            let f = FieldExtensionElement::from_coefficients([
+                C::BaseField::from_u64(i),
+                C::BaseField::from_u64(i * i + 1),
+            ]);
+            assert_eq!(
+                super::EllipticCurveElement::<C>::final_exponentiation(&f),
+                f.pow(C::TARGET_NORMALIZATION_POWER)
+            );
+        }
+    }
+
+    #[test]
+    fn final_exponentiation_matches_the_naive_one() {
+        assert_final_exponentiation_matches_the_naive_one::<Curve59>();
+        assert_final_exponentiation_matches_the_naive_one::<Curve43>();
+        assert_final_exponentiation_matches_the_naive_one::<Curve103>();
+    }
+
+    /// Returns whether `point` is a multiple of the generator. Among the points of
+    /// order r, only those of the curve over the base field are.
+    fn is_multiple_of_the_generator(point: &EllipticCurveElement) -> bool {
+        let g = EllipticCurveElement::generator();
+        (0..Curve59::ORDER_R).any(|k| g.operate_with_self(k) == *point)
+    }
+
+    #[test]
+    fn random_points_are_multiples_of_the_generator() {
+        for _ in 0..20 {
+            let point = EllipticCurveElement::random();
+            assert!(!point.is_neutral_element());
+            assert!(is_multiple_of_the_generator(&point));
+        }
+    }
+
+    #[test]
+    fn hash_to_curve_lands_in_the_multiples_of_the_generator() {
+        for i in 0_u8..10 {
+            let point = EllipticCurveElement::hash_to_curve(&[i], b"PINOCCHIO-TEST");
+            assert!(!point.is_neutral_element());
+            assert!(is_multiple_of_the_generator(&point));
+        }
+    }
+
+    #[test]
+    fn clear_cofactor_maps_every_point_to_the_multiples_of_the_generator() {
+        // (0, 0) has order 2, which divides the cofactor.
+        let order_2 = EllipticCurveElement::from_affine(FEE::zero(), FEE::zero()).unwrap();
+        assert!(order_2.clear_cofactor().is_neutral_element());
+        // Half of these have y outside the base field.
+        for x in 1..59 {
+            for sign in [false, true] {
+                let x = FEE::new_base(FE::from_u64(x));
+                if let Some(point) = EllipticCurveElement::from_x(x, sign) {
+                    assert!(is_multiple_of_the_generator(&point.clear_cofactor()));
+                }
+            }
+        }
+        // The image of the distortion map has order r but is killed by the trace.
+        let distorted = EllipticCurveElement::distorsion_map(&EllipticCurveElement::generator());
+        assert!(!distorted.is_neutral_element());
+        assert!(!is_multiple_of_the_generator(&distorted));
+        assert!(distorted.clear_cofactor().is_neutral_element());
+    }
 }
@@ -0,0 +1,230 @@
+use super::field_element::FieldElementError;
+use super::unsigned_integer::{bit_length, IsUnsignedInteger};
+use std::fmt::Debug;
+use std::ops;
+
+/// Operations shared by every field used in the proving system.
+/// Polynomials, circuits, setup, prover and verifier are generic over it,
+/// so that they can work with any choice of scalar field.
+pub trait Field:
+    Clone
+    + Debug
+    + PartialEq
+    + Eq
+    + Send
+    + Sync
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+    + ops::Neg<Output = Self>
+{
+    /// Degree of the field as an extension of its prime subfield.
+    const EXTENSION_DEGREE: usize = 1;
+
+    /// Returns the additive neutral element.
+    fn zero() -> Self;
+
+    /// Returns the multiplicative neutral element.
+    fn one() -> Self;
+
+    /// Returns the element obtained by adding `one()` `value` times.
+    fn from_u64(value: u64) -> Self;
+
+    /// Computes the multiplicative inverse of `self`.
+    fn inv(&self) -> Result<Self, FieldElementError>;
+
+    /// Returns a random element from the field.
+    fn random() -> Self;
+
+    /// Returns the canonical encoding of the element as bytes.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Decodes an element from its canonical encoding, as returned by `to_bytes`.
+    /// Fails on encodings of the wrong length or of values outside the field.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError>;
+
+    /// Returns the length in bytes of the encoding of every element.
+    fn byte_length() -> usize {
+        Self::zero().to_bytes().len()
+    }
+
+    /// Returns `self` to the power of `exponent` using
+    /// right-to-left binary method for modular exponentiation.
+    fn pow<T: IsUnsignedInteger>(&self, mut exponent: T) -> Self {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let mut result = Self::one();
+        let mut base = self.clone();
+
+        while exponent > zero {
+            // exponent % 2 == 1
+            if exponent & one == one {
+                result = result * base.clone();
+            }
+            // exponent = exponent / 2
+            exponent = exponent >> 1;
+            base = base.clone() * base;
+        }
+        result
+    }
+}
+
+/// A field whose Frobenius endomorphism x -> x^p, where p is the characteristic,
+/// can be computed without exponentiating.
+/// Prime fields are fixed by it, and in an extension F[u] / (u^d - non_residue)
+/// it maps `c_i * u^i` to `c_i^p * (u^(p - 1))^i * u^i`, with `u^(p - 1)` precomputed.
+pub trait FrobeniusField: Field {
+    /// Returns `self^p`.
+    fn frobenius(&self) -> Self;
+
+    /// Returns `self^(p^k)`.
+    fn frobenius_power(&self, k: usize) -> Self {
+        (0..k).fold(self.clone(), |x, _| x.frobenius())
+    }
+}
+
+/// A field of prime order. Its elements are the integers modulo the order,
+/// so each one has a canonical integer representative.
+pub trait PrimeField: Field + Copy {
+    type RepresentativeType: IsUnsignedInteger;
+
+    /// Returns the representative of `self` in the range [0, order).
+    fn representative(&self) -> Self::RepresentativeType;
+
+    /// Returns the number of bits of the largest representative, `order - 1`.
+    fn representative_bits() -> usize {
+        bit_length((-Self::one()).representative())
+    }
+
+    /// Computes the Legendre symbol of `self` with Euler's criterion:
+    /// 0 for zero, 1 for non-zero squares and -1 for non-squares.
+    fn legendre(&self) -> i8 {
+        if *self == Self::zero() {
+            return 0;
+        }
+        // self^((p - 1) / 2)
+        let symbol = self.pow((-Self::one()).representative() >> 1);
+        if symbol == Self::one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Returns `true` if `self` has a square root in the field.
+    fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Computes a square root of `self`, or `None` if it is not a square.
+    /// For p = 3 mod 4 it is just self^((p + 1) / 4). Otherwise it
+    /// uses the Tonelli-Shanks algorithm.
+    /// See "Handbook of Applied Cryptography", Algorithm 3.34.
+    fn sqrt(&self) -> Option<Self> {
+        let zero = Self::RepresentativeType::from(0);
+        let one = Self::RepresentativeType::from(1);
+        let three = Self::RepresentativeType::from(3);
+        let p_minus_one = (-Self::one()).representative();
+        if p_minus_one == one {
+            // Every element of F_2 is its own square root.
+            return Some(*self);
+        }
+        if !self.is_square() {
+            return None;
+        }
+        if *self == Self::zero() {
+            return Some(*self);
+        }
+
+        if p_minus_one & three == Self::RepresentativeType::from(2) {
+            // (p + 1) / 4 = (p - 1) / 4 + 1 when p = 3 mod 4
+            return Some(self.pow(p_minus_one >> 2) * *self);
+        }
+
+        // p - 1 = q * 2^s with q odd
+        let mut s = 0;
+        let mut q = p_minus_one;
+        while q & one == zero {
+            q = q >> 1;
+            s += 1;
+        }
+        let non_square = (2..)
+            .map(Self::from_u64)
+            .find(|z| z.legendre() == -1)
+            .expect("Half of the non-zero elements are non-squares.");
+
+        let mut m = s;
+        let mut c = non_square.pow(q);
+        let mut t = self.pow(q);
+        // self^((q + 1) / 2)
+        let mut root = self.pow(q >> 1) * *self;
+        while t != Self::one() {
+            // Least i such that t^(2^i) = 1, which is smaller than m.
+            let mut i = 0;
+            let mut t_power = t;
+            while t_power != Self::one() {
+                t_power = t_power * t_power;
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b * b;
+            }
+            m = i;
+            c = b * b;
+            t = t * c;
+            root = root * b;
+        }
+        Some(root)
+    }
+}
+
+/// A field where the y coordinate of a compressed point can be recovered:
+/// it has square roots, and a sign that tells a non-zero element apart from its negation.
+pub trait SqrtField: Field {
+    /// Computes a square root of `self`, or `None` if it is not a square.
+    fn sqrt(&self) -> Option<Self>;
+
+    /// Returns whether `self` is larger than its negation, comparing the
+    /// coefficient of highest degree first and the next one when it is zero.
+    fn is_lexicographically_largest(&self) -> bool;
+
+    /// Returns the number of most significant bits of `to_bytes` that are zero
+    /// for every element, which encodings of points can use for their flags.
+    fn unused_leading_bits() -> usize;
+}
+
+/// Values that can be swapped depending on a secret bit without branching on it.
+pub trait ConditionalSwap {
+    /// Swaps `a` and `b` when `mask` has all its bits set and leaves them as they are
+    /// when it is zero, running the same operations in both cases.
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64);
+}
+
+/// Returns the mask that makes `ConditionalSwap` swap when `bit` is set:
+/// all ones if it is set and zero otherwise.
+pub fn swap_mask(bit: bool) -> u64 {
+    (bit as u64).wrapping_neg()
+}
+
+/// Swaps the bits of `a` and `b` selected by `mask`.
+pub(crate) fn conditional_swap_u64(a: &mut u64, b: &mut u64, mask: u64) {
+    let t = (*a ^ *b) & mask;
+    *a ^= t;
+    *b ^= t;
+}
+
+impl<F: PrimeField> SqrtField for F {
+    fn sqrt(&self) -> Option<Self> {
+        PrimeField::sqrt(self)
+    }
+
+    fn is_lexicographically_largest(&self) -> bool {
+        self.representative() > (-*self).representative()
+    }
+
+    fn unused_leading_bits() -> usize {
+        8 * Self::byte_length() - Self::representative_bits()
+    }
+}
@@ -1,4 +1,5 @@
-use super::cyclic_group::CyclicBilinearGroup;
+use super::cyclic_group::{CyclicGroup, Group, PairingEngine};
+use super::field::{ConditionalSwap, Field, FrobeniusField, PrimeField};
 use rand::prelude::*;
 use std::ops;
 
@@ -6,6 +7,7 @@ use std::ops;
 pub enum FieldElementError {
     OutOfRangeValue,
     DivisionByZero,
+    InvalidLength,
 }
 
 /// Represents an element in Fp. (E.g: 0, 1, 2 are the elements of F3)
@@ -28,6 +30,9 @@ impl<const ORDER: u128> FieldElement<ORDER> {
         self.value
     }
 
+    /// Minimum number of bytes needed to encode `ORDER - 1`, and at least one.
+    const BYTE_LENGTH: usize = (128 - ((ORDER - 1) | 1).leading_zeros() as usize).div_ceil(8);
+
     /// Returns a random element from the field.
     pub fn random() -> Self {
         let value: u128 = rand::thread_rng().gen_range(1..ORDER);
@@ -111,33 +116,133 @@ impl<const ORDER: u128> ops::Div for FieldElement<ORDER> {
     }
 }
 
-impl<const ORDER: u128> CyclicBilinearGroup for FieldElement<ORDER> {
-    type PairingOutput = Self;
+impl<const ORDER: u128> Field for FieldElement<ORDER> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
 
-    fn generator() -> FieldElement<ORDER> {
-        FieldElement::new(1)
+    fn one() -> Self {
+        Self::new(1)
     }
 
-    fn neutral_element() -> FieldElement<ORDER> {
-        FieldElement::new(0)
+    fn from_u64(value: u64) -> Self {
+        Self::new(value.into())
     }
 
-    fn operate_with_self(&self, times: u128) -> Self {
-        FieldElement::new(times) * *self
+    fn inv(&self) -> Result<Self, FieldElementError> {
+        FieldElement::inv(*self)
     }
 
-    fn pairing(&self, other: &Self) -> Self {
-        *self * *other
+    fn random() -> Self {
+        FieldElement::random()
+    }
+
+    /// Encodes the representative in big-endian order using
+    /// the minimum number of bytes needed for `ORDER - 1`.
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_be_bytes()[16 - Self::BYTE_LENGTH..].to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError> {
+        if bytes.len() != Self::BYTE_LENGTH {
+            return Err(FieldElementError::InvalidLength);
+        }
+        let value = bytes
+            .iter()
+            .fold(0u128, |value, byte| (value << 8) | *byte as u128);
+        if value >= ORDER {
+            return Err(FieldElementError::OutOfRangeValue);
+        }
+        Ok(Self { value })
+    }
+}
+
+/// Elements of a prime field are fixed by the Frobenius map.
+impl<const ORDER: u128> FrobeniusField for FieldElement<ORDER> {
+    fn frobenius(&self) -> Self {
+        *self
+    }
+}
+
+impl<const ORDER: u128> ConditionalSwap for FieldElement<ORDER> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        let mask = ((mask as u128) << 64) | mask as u128;
+        let t = (a.value ^ b.value) & mask;
+        a.value ^= t;
+        b.value ^= t;
+    }
+}
+
+impl<const ORDER: u128> PrimeField for FieldElement<ORDER> {
+    type RepresentativeType = u128;
+
+    fn representative(&self) -> u128 {
+        self.value
+    }
+}
+
+impl<const ORDER: u128> Group for FieldElement<ORDER> {
+    fn neutral_element() -> FieldElement<ORDER> {
+        FieldElement::new(0)
     }
 
     fn operate_with(&self, other: &Self) -> Self {
         *self + *other
     }
+
+    fn inverse(&self) -> Self {
+        -*self
+    }
+}
+
+impl<const ORDER: u128> CyclicGroup for FieldElement<ORDER> {
+    type Scalar = Self;
+
+    fn generator() -> FieldElement<ORDER> {
+        FieldElement::new(1)
+    }
+
+    /// The whole field has order `ORDER`.
+    fn is_in_subgroup(&self) -> bool {
+        true
+    }
+}
+
+/// Symmetric pairing over the raw values, without any hiding.
+/// The target group is the field under addition, so it is only useful for testing.
+impl<const ORDER: u128> PairingEngine for FieldElement<ORDER> {
+    type Scalar = Self;
+    type G1 = Self;
+    type G2 = Self;
+    type GT = Self;
+    type G1Prepared = Self;
+    type G2Prepared = Self;
+
+    fn pairing(p: &Self, q: &Self) -> Self {
+        *p * *q
+    }
+
+    fn prepare_g1(p: &Self) -> Self {
+        *p
+    }
+
+    fn prepare_g2(q: &Self) -> Self {
+        *q
+    }
+
+    fn multi_pairing_prepared(pairs: &[(&Self, &Self)]) -> Self {
+        pairs
+            .iter()
+            .fold(Self::neutral_element(), |product, (p, q)| {
+                product.operate_with(&Self::pairing(p, q))
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::field::swap_mask;
     const ORDER: u128 = 13;
     type FE = FieldElement<ORDER>;
 
@@ -274,13 +379,113 @@ mod tests {
     ) {
         let a = FE::new(3);
         let b = FE::new(12);
-        assert_eq!(a * b, a.operate_with_self(12));
+        assert_eq!(a * b, a.operate_with_self(12u64));
+    }
+
+    #[test]
+    fn to_bytes_uses_minimum_width_for_the_order() {
+        assert_eq!(Field::to_bytes(&FE::new(12)), vec![12]);
+        assert_eq!(
+            Field::to_bytes(&FieldElement::<65537>::new(258)),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn from_bytes_inverts_to_bytes() {
+        for value in 0..ORDER {
+            let a = FE::new(value);
+            assert_eq!(FE::from_bytes(&Field::to_bytes(&a)), Ok(a));
+        }
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_encodings() {
+        assert_eq!(
+            FE::from_bytes(&[13]),
+            Err(FieldElementError::OutOfRangeValue)
+        );
+        assert_eq!(
+            FE::from_bytes(&[0, 1]),
+            Err(FieldElementError::InvalidLength)
+        );
+        assert_eq!(FE::from_bytes(&[]), Err(FieldElementError::InvalidLength));
+    }
+
+    #[test]
+    fn generic_pow_matches_pow() {
+        assert_eq!(Field::pow(&FE::new(2), 11u64), FE::new(2).pow(11));
     }
 
     #[test]
     fn field_element_as_group_element_pairing_works_as_multiplication_in_finite_fields() {
         let a = FE::new(3);
         let b = FE::new(12);
-        assert_eq!(a * b, a.pairing(&b));
+        assert_eq!(a * b, FE::pairing(&a, &b));
+    }
+
+    #[test]
+    fn field_element_as_group_element_multi_pairing_is_the_sum_of_products() {
+        let pairs = [(FE::new(3), FE::new(12)), (FE::new(5), FE::new(2))];
+        assert_eq!(FE::multi_pairing(&pairs), FE::new(36 + 10));
+        assert_eq!(FE::new(5).operate_with(&FE::new(5).inverse()), FE::new(0));
+    }
+
+    /// Checks `sqrt`, `legendre` and `is_square` on every element of the field.
+    fn assert_square_roots_are_right<const P: u128>() {
+        let mut squares = 0;
+        for value in 0..P {
+            let a = FieldElement::<P>::new(value);
+            match PrimeField::sqrt(&a) {
+                Some(root) => {
+                    assert_eq!(root * root, a);
+                    assert!(a.is_square());
+                    squares += 1;
+                }
+                None => {
+                    assert_eq!(a.legendre(), -1);
+                    assert!(!a.is_square());
+                }
+            }
+        }
+        assert_eq!(squares, P / 2 + 1);
+    }
+
+    #[test]
+    fn sqrt_works_when_p_is_3_mod_4() {
+        assert_square_roots_are_right::<59>();
+        assert_square_roots_are_right::<103>();
+    }
+
+    #[test]
+    fn sqrt_works_with_tonelli_shanks() {
+        // p - 1 = 3 * 2^2, 2^4, 3 * 2^5 and 2^8
+        assert_square_roots_are_right::<13>();
+        assert_square_roots_are_right::<17>();
+        assert_square_roots_are_right::<97>();
+        assert_square_roots_are_right::<257>();
+    }
+
+    #[test]
+    fn sqrt_works_in_the_field_of_two_elements() {
+        assert_square_roots_are_right::<2>();
+    }
+
+    #[test]
+    fn legendre_symbol_of_small_values() {
+        assert_eq!(FE::new(0).legendre(), 0);
+        assert_eq!(FE::new(4).legendre(), 1);
+        assert_eq!(FE::new(10).legendre(), 1);
+        // 2 is not a square modulo 13 since 13 = 5 mod 8.
+        assert_eq!(FE::new(2).legendre(), -1);
+    }
+
+    #[test]
+    fn conditional_swap_only_swaps_with_a_full_mask() {
+        let (mut a, mut b) = (FE::new(3), FE::new(7));
+        FE::conditional_swap(&mut a, &mut b, swap_mask(false));
+        assert_eq!((a, b), (FE::new(3), FE::new(7)));
+        FE::conditional_swap(&mut a, &mut b, swap_mask(true));
+        assert_eq!((a, b), (FE::new(7), FE::new(3)));
     }
 }
@@ -1,27 +1,43 @@
-use super::super::config::{ORDER_FIELD_EXTENSION, ORDER_P};
-use super::{field_element::FieldElement, polynomial::Polynomial};
+use super::cyclic_group::Group;
+use super::field::{ConditionalSwap, Field, FrobeniusField, PrimeField, SqrtField};
+use super::field_element::FieldElementError;
+use super::polynomial::Polynomial;
 use std::ops;
 
-type FE = FieldElement<ORDER_P>;
-
-/// Represents an element in an extension of a prime field
-/// as polynomials modulo a defining polynomial.
-#[derive(Debug, Clone)]
-pub struct FieldExtensionElement {
-    value: Polynomial<ORDER_P>,
+/// Represents an element in the quadratic extension F[X] / (X^2 + 1)
+/// of a base field `F`, as the coefficients `[a, b]` of `a + b * X`.
+/// They live in a fixed-size array, so that arithmetic does not allocate.
+/// For it to be a field, -1 has to be a quadratic non-residue in `F`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldExtensionElement<F: Field> {
+    value: [F; 2],
 }
 
-impl FieldExtensionElement {
+impl<F: Field> FieldExtensionElement<F> {
     /// Creates a `FieldExtensionElement` from a polynomial `p`.
     /// It keeps the remainder of dividing `p` by the defining polynomial.
-    pub fn new(p: Polynomial<ORDER_P>) -> Self {
-        let (_quotient, remainder) = p.long_division_with_remainder(&Self::defining_polynomial());
-        Self { value: remainder }
+    pub fn new(p: Polynomial<F>) -> Self {
+        // X^(2k) = (-1)^k and X^(2k + 1) = (-1)^k * X
+        let mut value = [F::zero(), F::zero()];
+        for (i, coefficient) in p.coefficients().iter().enumerate() {
+            let coefficient = if (i / 2) % 2 == 0 {
+                coefficient.clone()
+            } else {
+                -coefficient.clone()
+            };
+            value[i % 2] = value[i % 2].clone() + coefficient;
+        }
+        Self { value }
+    }
+
+    /// Creates the element `a + b * X` from its coefficients `[a, b]`.
+    pub fn from_coefficients(value: [F; 2]) -> Self {
+        Self { value }
     }
 
-    /// Creates a `FieldExtensionElement` belonging to the base prime field.
-    pub fn new_base(value: u128) -> Self {
-        Self::new(Polynomial::new_monomial(FE::new(value), 0))
+    /// Creates a `FieldExtensionElement` belonging to the base field.
+    pub fn new_base(value: F) -> Self {
+        Self::from_coefficients([value, F::zero()])
     }
 
     /// Returns the defining polynomial of the field. In this case:
@@ -30,14 +46,43 @@ impl FieldExtensionElement {
     /// This polynomial is chosen this way because the resulting field extension
     /// is of degree 2. With this property a type I pairing compatible elliptic curve
     /// is then defined.
-    pub fn defining_polynomial() -> Polynomial<ORDER_P> {
-        Polynomial::new(vec![FE::new(1), FE::new(0), FE::new(1)])
+    pub fn defining_polynomial() -> Polynomial<F> {
+        Polynomial::new(vec![F::one(), F::zero(), F::one()])
+    }
+
+    /// Returns the coefficients `[a, b]` of `self = a + b * X`.
+    pub fn coefficients(&self) -> [F; 2] {
+        self.value.clone()
+    }
+
+    /// Returns `a - b * X` for `self = a + b * X`.
+    pub fn conjugate(&self) -> Self {
+        let [a, b] = self.coefficients();
+        Self::from_coefficients([a, -b])
+    }
+
+    /// Computes the inverse of `a + b * X` as `(a - b * X) / (a^2 + b^2)`.
+    /// The norm `(a + b * X) * (a - b * X) = a^2 + b^2` lies in the base field,
+    /// so a single inversion there is enough.
+    pub fn inv(self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert the zero element.");
+        let [a, b] = self.coefficients();
+        let norm_inverse = (a.clone() * a + b.clone() * b)
+            .inv()
+            .expect("-1 is not a square in the base field");
+        Self::from_coefficients(self.conjugate().value.map(|c| c * norm_inverse.clone()))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value
+            .iter()
+            .all(|coefficient| *coefficient == F::zero())
     }
 
     /// Returns `self` to the power of `exponent` using
     /// right-to-left binary method for modular exponentiation.
     pub fn pow(&self, mut exponent: u128) -> Self {
-        let mut result = Self::new(Polynomial::new_monomial(FE::new(1), 0));
+        let mut result = Self::new_base(F::one());
         let mut base = self.clone();
 
         while exponent > 0 {
@@ -51,180 +96,350 @@ impl FieldExtensionElement {
         }
         result
     }
+}
 
-    pub fn inv(self) -> Self {
-        assert_ne!(
-            self.value,
-            Polynomial::zero(),
-            "Cannot invert the zero element."
-        );
-        self.pow(ORDER_FIELD_EXTENSION - 2)
+impl<F: Field> FieldExtensionElement<F> {
+    /// Squares an element of norm one, `a^2 + b^2 = 1`, as
+    /// `(2 * a^2 - 1) + ((a + b)^2 - 1) * X`, with two squarings in the base field.
+    /// These are the values of the pairing after the easy part of the final exponentiation.
+    pub fn cyclotomic_square(&self) -> Self {
+        let [a, b] = self.coefficients();
+        let a_squared = a.clone() * a.clone();
+        let a_plus_b = a + b;
+        Self::from_coefficients([
+            a_squared.clone() + a_squared - F::one(),
+            a_plus_b.clone() * a_plus_b - F::one(),
+        ])
+    }
+
+    /// Returns `self` to the power of `exponent`, for `self` of norm one,
+    /// using `cyclotomic_square` in the left-to-right binary method.
+    pub fn cyclotomic_pow(&self, exponent: u128) -> Self {
+        (0..128 - exponent.leading_zeros())
+            .rev()
+            .fold(Self::one(), |result, i| {
+                let result = result.cyclotomic_square();
+                if (exponent >> i) & 1 == 1 {
+                    result * self
+                } else {
+                    result
+                }
+            })
     }
 }
 
-impl PartialEq for FieldExtensionElement {
-    fn eq(&self, other: &Self) -> bool {
-        self.value == other.value
+impl<F: PrimeField> FieldExtensionElement<F> {
+    /// Returns the norm `a^2 + b^2` of `self = a + b * X`, which lies in the base field.
+    fn norm(&self) -> F {
+        let [a, b] = self.coefficients();
+        a * a + b * b
+    }
+
+    /// Computes the Legendre symbol of `self`: 0 for zero, 1 for non-zero
+    /// squares and -1 for non-squares. An element of the extension is a
+    /// square exactly when its norm is a square in the base field.
+    pub fn legendre(&self) -> i8 {
+        self.norm().legendre()
+    }
+
+    /// Returns `true` if `self` has a square root in the extension.
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Computes a square root of `self`, or `None` if it is not a square.
+    /// If (x0 + x1 * X)^2 = a + b * X, then x0^2 = (a +- sqrt(a^2 + b^2)) / 2
+    /// and x1 = b / (2 * x0), so it only needs square roots in the base field.
+    /// See "Square root computation over even extension fields", Algorithm 8.
+    pub fn sqrt(&self) -> Option<Self> {
+        let [a, b] = self.coefficients();
+        if b == F::zero() {
+            // Since -1 is not a square, either a or -a is.
+            return match a.sqrt() {
+                Some(root) => Some(Self::new_base(root)),
+                None => (-a)
+                    .sqrt()
+                    .map(|root| Self::from_coefficients([F::zero(), root])),
+            };
+        }
+        let norm_root = self.norm().sqrt()?;
+        let two = F::from_u64(2);
+        let mut x0_squared = (a + norm_root) / two;
+        if !x0_squared.is_square() {
+            x0_squared = (a - norm_root) / two;
+        }
+        let x0 = x0_squared
+            .sqrt()
+            .expect("One of the two candidates is a square.");
+        // x0 is not zero, since otherwise the square would have b = 0.
+        let x1 = b / (two * x0);
+        Some(Self::from_coefficients([x0, x1]))
     }
 }
-impl Eq for FieldExtensionElement {}
 
-impl ops::Add<&FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+/// The sign compares the coefficient of X first, and the encoding starts with
+/// the constant term, so its unused bits are those of the base field.
+impl<F: PrimeField> SqrtField for FieldExtensionElement<F> {
+    fn sqrt(&self) -> Option<Self> {
+        FieldExtensionElement::sqrt(self)
+    }
 
-    fn add(self, a_field_element: &FieldExtensionElement) -> Self::Output {
-        Self::Output {
-            value: &a_field_element.value + &self.value,
+    fn is_lexicographically_largest(&self) -> bool {
+        let [a, b] = self.coefficients();
+        if b == F::zero() {
+            a.is_lexicographically_largest()
+        } else {
+            b.is_lexicographically_largest()
         }
     }
+
+    fn unused_leading_bits() -> usize {
+        F::unused_leading_bits()
+    }
+}
+
+impl<F: Field> ops::Add<&FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
+
+    fn add(self, a_field_element: &FieldExtensionElement<F>) -> Self::Output {
+        let [a0, a1] = &self.value;
+        let [b0, b1] = &a_field_element.value;
+        Self::Output::from_coefficients([a0.clone() + b0.clone(), a1.clone() + b1.clone()])
+    }
 }
 
-impl ops::Add<FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Add<FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn add(self, a_field_element: FieldExtensionElement) -> Self::Output {
+    fn add(self, a_field_element: FieldExtensionElement<F>) -> Self::Output {
         &self + &a_field_element
     }
 }
 
-impl ops::Add<&FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Add<&FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn add(self, a_field_element: &FieldExtensionElement) -> Self::Output {
+    fn add(self, a_field_element: &FieldExtensionElement<F>) -> Self::Output {
         &self + a_field_element
     }
 }
 
-impl ops::Add<FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Add<FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn add(self, a_field_element: FieldExtensionElement) -> Self::Output {
+    fn add(self, a_field_element: FieldExtensionElement<F>) -> Self::Output {
         self + &a_field_element
     }
 }
 
-impl ops::Neg for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Neg for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
     fn neg(self) -> Self::Output {
-        Self::Output {
-            value: -self.value.clone(),
-        }
+        let [a0, a1] = self.value.clone();
+        Self::Output::from_coefficients([-a0, -a1])
     }
 }
 
-impl ops::Neg for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Neg for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
     fn neg(self) -> Self::Output {
         -&self
     }
 }
 
-impl ops::Sub<&FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Sub<&FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn sub(self, substrahend: &FieldExtensionElement) -> Self::Output {
+    fn sub(self, substrahend: &FieldExtensionElement<F>) -> Self::Output {
         self + &(-substrahend)
     }
 }
 
-impl ops::Sub<FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Sub<FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn sub(self, substrahend: FieldExtensionElement) -> Self::Output {
+    fn sub(self, substrahend: FieldExtensionElement<F>) -> Self::Output {
         &self - &substrahend
     }
 }
 
-impl ops::Sub<&FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Sub<&FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn sub(self, substrahend: &FieldExtensionElement) -> Self::Output {
+    fn sub(self, substrahend: &FieldExtensionElement<F>) -> Self::Output {
         &self - substrahend
     }
 }
 
-impl ops::Sub<FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Sub<FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn sub(self, substrahend: FieldExtensionElement) -> Self::Output {
+    fn sub(self, substrahend: FieldExtensionElement<F>) -> Self::Output {
         self - &substrahend
     }
 }
 
-impl ops::Mul<&FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Mul<&FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn mul(self, a_field_extension_element: &FieldExtensionElement) -> Self::Output {
-        let p = self.value.mul_with_ref(&a_field_extension_element.value);
-        Self::Output::new(p)
+    /// Karatsuba multiplication: three multiplications in the base field.
+    /// (a0 + a1 * X) * (b0 + b1 * X) = (a0 * b0 - a1 * b1) + (a0 * b1 + a1 * b0) * X
+    /// where a0 * b1 + a1 * b0 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1.
+    fn mul(self, a_field_extension_element: &FieldExtensionElement<F>) -> Self::Output {
+        let [a0, a1] = &self.value;
+        let [b0, b1] = &a_field_extension_element.value;
+        let v0 = a0.clone() * b0.clone();
+        let v1 = a1.clone() * b1.clone();
+        let c1 = (a0.clone() + a1.clone()) * (b0.clone() + b1.clone()) - v0.clone() - v1.clone();
+        Self::Output::from_coefficients([v0 - v1, c1])
     }
 }
 
-impl ops::Mul<FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Mul<FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn mul(self, a_field_extension_element: FieldExtensionElement) -> Self::Output {
+    fn mul(self, a_field_extension_element: FieldExtensionElement<F>) -> Self::Output {
         &self * &a_field_extension_element
     }
 }
 
-impl ops::Mul<&FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Mul<&FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn mul(self, a_field_extension_element: &FieldExtensionElement) -> Self::Output {
+    fn mul(self, a_field_extension_element: &FieldExtensionElement<F>) -> Self::Output {
         &self * a_field_extension_element
     }
 }
 
-impl ops::Mul<FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Mul<FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn mul(self, a_field_extension_element: FieldExtensionElement) -> Self::Output {
+    fn mul(self, a_field_extension_element: FieldExtensionElement<F>) -> Self::Output {
         self * &a_field_extension_element
     }
 }
 
-impl ops::Div<&FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Div<&FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
     #[allow(clippy::suspicious_arithmetic_impl)]
-    fn div(self, dividend: &FieldExtensionElement) -> Self::Output {
+    fn div(self, dividend: &FieldExtensionElement<F>) -> Self::Output {
         self * &dividend.clone().inv()
     }
 }
 
-impl ops::Div<FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Div<FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn div(self, dividend: FieldExtensionElement) -> Self::Output {
+    fn div(self, dividend: FieldExtensionElement<F>) -> Self::Output {
         &self / &dividend
     }
 }
 
-impl ops::Div<FieldExtensionElement> for &FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Div<FieldExtensionElement<F>> for &FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn div(self, dividend: FieldExtensionElement) -> Self::Output {
+    fn div(self, dividend: FieldExtensionElement<F>) -> Self::Output {
         self / &dividend
     }
 }
 
-impl ops::Div<&FieldExtensionElement> for FieldExtensionElement {
-    type Output = FieldExtensionElement;
+impl<F: Field> ops::Div<&FieldExtensionElement<F>> for FieldExtensionElement<F> {
+    type Output = FieldExtensionElement<F>;
 
-    fn div(self, dividend: &FieldExtensionElement) -> Self::Output {
+    fn div(self, dividend: &FieldExtensionElement<F>) -> Self::Output {
         &self / dividend
     }
 }
 
+impl<F: Field> Field for FieldExtensionElement<F> {
+    const EXTENSION_DEGREE: usize = 2 * F::EXTENSION_DEGREE;
+
+    fn zero() -> Self {
+        Self::new_base(F::zero())
+    }
+
+    fn one() -> Self {
+        Self::new_base(F::one())
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::new_base(F::from_u64(value))
+    }
+
+    fn inv(&self) -> Result<Self, FieldElementError> {
+        if self.is_zero() {
+            Err(FieldElementError::DivisionByZero)
+        } else {
+            Ok(self.clone().inv())
+        }
+    }
+
+    fn random() -> Self {
+        Self::from_coefficients([F::random(), F::random()])
+    }
+
+    /// Concatenates the encodings of the coefficients, constant term first.
+    fn to_bytes(&self) -> Vec<u8> {
+        self.coefficients()
+            .iter()
+            .flat_map(|coefficient| coefficient.to_bytes())
+            .collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError> {
+        let length = F::byte_length();
+        if bytes.len() != 2 * length {
+            return Err(FieldElementError::InvalidLength);
+        }
+        let a = F::from_bytes(&bytes[..length])?;
+        let b = F::from_bytes(&bytes[length..])?;
+        Ok(Self::from_coefficients([a, b]))
+    }
+}
+
+/// Since -1 is not a square, p = 3 mod 4 and X^p = X * (-1)^((p - 1) / 2) = -X.
+/// So the Frobenius map is the conjugation of the coefficients' images.
+impl<F: Field + ConditionalSwap> ConditionalSwap for FieldExtensionElement<F> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        for (x, y) in a.value.iter_mut().zip(b.value.iter_mut()) {
+            F::conditional_swap(x, y, mask);
+        }
+    }
+}
+
+impl<F: FrobeniusField> FrobeniusField for FieldExtensionElement<F> {
+    fn frobenius(&self) -> Self {
+        let [a, b] = self.coefficients();
+        Self::from_coefficients([a.frobenius(), -b.frobenius()])
+    }
+}
+
+/// The multiplicative group of the extension, where pairings take their values.
+impl<F: Field> Group for FieldExtensionElement<F> {
+    fn neutral_element() -> Self {
+        Self::one()
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        self.clone() * other.clone()
+    }
+
+    fn inverse(&self) -> Self {
+        self.clone().inv()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::field_element::FieldElement;
 
+    type FE = FieldElement<59>;
     #[allow(clippy::upper_case_acronyms)]
-    type FEE = FieldExtensionElement;
+    type FEE = FieldExtensionElement<FE>;
 
     #[test]
     fn test_creating_a_field_element_extension_gets_the_remainder_of_the_polynomial() {
@@ -331,4 +546,124 @@ mod tests {
         let expected_result = FEE::new(Polynomial::new(vec![FE::new(28), FE::new(8)]));
         assert_eq!(a.inv(), expected_result);
     }
+
+    #[test]
+    fn test_inverting_zero_through_the_field_trait_is_an_error() {
+        assert_eq!(
+            Field::inv(&FEE::zero()),
+            Err(FieldElementError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn from_bytes_inverts_to_bytes() {
+        let a = FEE::new(Polynomial::new(vec![FE::new(12), FE::new(5)]));
+        assert_eq!(a.to_bytes(), vec![12, 5]);
+        assert_eq!(FEE::from_bytes(&a.to_bytes()), Ok(a));
+        assert_eq!(FEE::from_bytes(&[0, 0]), Ok(FEE::zero()));
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_encodings() {
+        assert_eq!(
+            FEE::from_bytes(&[12, 59]),
+            Err(FieldElementError::OutOfRangeValue)
+        );
+        assert_eq!(
+            FEE::from_bytes(&[12, 5, 0]),
+            Err(FieldElementError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn sqrt_finds_the_roots_of_every_square() {
+        let mut squares = 0;
+        for a0 in 0..59 {
+            for a1 in 0..59 {
+                let a = FEE::new(Polynomial::new(vec![FE::new(a0), FE::new(a1)]));
+                match a.sqrt() {
+                    Some(root) => {
+                        assert_eq!(&root * &root, a);
+                        assert!(a.is_square());
+                        squares += 1;
+                    }
+                    None => assert_eq!(a.legendre(), -1),
+                }
+            }
+        }
+        // Zero and half of the non-zero elements.
+        assert_eq!(squares, (59 * 59 + 1) / 2);
+    }
+
+    #[test]
+    fn sign_compares_the_coefficient_of_x_first() {
+        let largest = |a0, a1| {
+            FEE::new(Polynomial::new(vec![FE::new(a0), FE::new(a1)])).is_lexicographically_largest()
+        };
+        // 31 > 59 - 31, so the sign is set by a coefficient of 31.
+        assert!(largest(1, 31));
+        assert!(!largest(31, 1));
+        assert!(largest(31, 0));
+        assert!(!largest(0, 0));
+    }
+
+    #[test]
+    fn elements_of_the_base_field_are_squares_in_the_extension() {
+        // 2 is not a square in F_59, so its root is a multiple of X.
+        let two = FEE::from_u64(2);
+        let root = two.sqrt().unwrap();
+        assert_eq!(root.coefficients()[0], FE::new(0));
+        assert_eq!(&root * &root, two);
+    }
+
+    #[test]
+    fn norm_inversion_matches_extended_euclid() {
+        for (a0, a1) in [(1, 0), (0, 1), (12, 5), (58, 58), (31, 7)] {
+            let value = Polynomial::new(vec![FE::new(a0), FE::new(a1)]);
+            let expected = value.inverse_modulo(&FEE::defining_polynomial()).unwrap();
+            assert_eq!(FEE::new(value).inv(), FEE::new(expected));
+        }
+    }
+
+    #[test]
+    fn karatsuba_multiplication_matches_polynomial_multiplication() {
+        for _ in 0..20 {
+            let [a, b] = [FEE::random(), FEE::random()];
+            let p = Polynomial::new(a.coefficients().to_vec());
+            let q = Polynomial::new(b.coefficients().to_vec());
+            assert_eq!(&a * &b, FEE::new(p.mul_with_ref(&q)));
+        }
+    }
+
+    #[test]
+    fn new_reduces_polynomials_of_any_degree() {
+        // 1 + 2X + 3X^2 + 4X^3 + 5X^4 = (1 - 3 + 5) + (2 - 4) * X
+        let p = Polynomial::new((1..=5).map(FE::new).collect());
+        let expected = FEE::from_coefficients([FE::new(3), -FE::new(2)]);
+        assert_eq!(FEE::new(p.clone()), expected);
+        let (_quotient, remainder) = p.long_division_with_remainder(&FEE::defining_polynomial());
+        assert_eq!(FEE::new(remainder), expected);
+    }
+
+    #[test]
+    fn frobenius_is_the_conjugate() {
+        let a = FEE::from_coefficients([FE::new(12), FE::new(5)]);
+        assert_eq!(
+            a.conjugate(),
+            FEE::from_coefficients([FE::new(12), -FE::new(5)])
+        );
+        assert_eq!(a.frobenius(), a.pow(59));
+        assert_eq!(&a * &a.conjugate(), FEE::from_u64(12 * 12 + 5 * 5));
+    }
+
+    #[test]
+    fn cyclotomic_square_matches_square_for_elements_of_norm_one() {
+        let a = FEE::from_coefficients([FE::new(12), FE::new(5)]);
+        // a^(p - 1) = conj(a) / a has norm one.
+        let a = a.conjugate() / a;
+        assert_eq!(&a * &a.conjugate(), FEE::one());
+        assert_eq!(a.cyclotomic_square(), &a * &a);
+        assert_eq!(a.cyclotomic_pow(12345), a.pow(12345));
+        assert_eq!(a.cyclotomic_pow(0), FEE::one());
+    }
 }
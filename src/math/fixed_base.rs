@@ -0,0 +1,163 @@
+use super::cyclic_group::CyclicGroup;
+use super::field::{swap_mask, ConditionalSwap, PrimeField};
+use super::unsigned_integer::{bit_length, window_value};
+
+/// Largest window size considered when choosing one automatically.
+const MAX_WINDOW_SIZE: usize = 16;
+
+/// Precomputed multiples of a fixed base `g` for fast scalar multiplication.
+///
+/// Scalars are split in windows of `window_size` bits, and row `i` of the table
+/// holds `j * 2^(i * window_size) * g` for every `j` that fits in a window.
+/// A scalar multiplication is then one table lookup and one group operation
+/// per window, without any doubling.
+/// See "Handbook of Applied Cryptography", section 14.6.3 (fixed-base windowing).
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable<G: CyclicGroup> {
+    base: G,
+    window_size: usize,
+    table: Vec<Vec<G>>,
+}
+
+impl<G: CyclicGroup> FixedBaseTable<G> {
+    /// Builds the table of `base` with the window size that minimizes the
+    /// total work of building it and then computing `number_of_multiplications`
+    /// scalar multiplications with it.
+    pub fn new(base: &G, number_of_multiplications: usize) -> Self {
+        let window_size = optimal_window_size(scalar_bits::<G>(), number_of_multiplications);
+        Self::with_window_size(base, window_size)
+    }
+
+    /// Builds the table of `base` with windows of `window_size` bits.
+    pub fn with_window_size(base: &G, window_size: usize) -> Self {
+        assert!(window_size > 0, "The window size must be positive.");
+        let number_of_windows = scalar_bits::<G>().div_ceil(window_size);
+        let mut table = Vec::with_capacity(number_of_windows);
+        let mut window_base = base.clone();
+        for _ in 0..number_of_windows {
+            let mut row = Vec::with_capacity(1 << window_size);
+            row.push(G::neutral_element());
+            for j in 1..(1 << window_size) {
+                row.push(row[j - 1].operate_with(&window_base));
+            }
+            // 2^window_size * window_base
+            window_base = row[(1 << window_size) - 1].operate_with(&window_base);
+            table.push(row);
+        }
+        Self {
+            base: base.clone(),
+            window_size,
+            table,
+        }
+    }
+
+    /// Returns the base of the table.
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    /// Computes `scalar * base`, with additive notation.
+    pub fn operate_with_scalar(&self, scalar: &G::Scalar) -> G {
+        let k = scalar.representative();
+        let bits = bit_length(k);
+        self.table
+            .iter()
+            .enumerate()
+            .take_while(|(i, _)| i * self.window_size < bits)
+            .map(|(i, row)| &row[window_value(k, i * self.window_size, self.window_size)])
+            .fold(G::neutral_element(), |acc, x| acc.operate_with(x))
+    }
+
+    /// Computes `scalar * base` like `operate_with_scalar`, with a sequence of
+    /// operations that does not depend on the scalar: every window reads its whole
+    /// row, keeping the entry it needs with `ConditionalSwap`, and is added even
+    /// when it is zero. Use it when the scalar is secret, like the toxic waste.
+    /// Note that the group arithmetic itself is not constant time.
+    pub fn operate_with_secret_scalar(&self, scalar: &G::Scalar) -> G
+    where
+        G: ConditionalSwap,
+    {
+        let k = scalar.representative();
+        self.table
+            .iter()
+            .enumerate()
+            .fold(G::neutral_element(), |acc, (i, row)| {
+                let index = window_value(k, i * self.window_size, self.window_size);
+                let mut selected = G::neutral_element();
+                for (j, entry) in row.iter().enumerate() {
+                    let mut entry = entry.clone();
+                    G::conditional_swap(&mut selected, &mut entry, swap_mask(j == index));
+                }
+                acc.operate_with(&selected)
+            })
+    }
+}
+
+/// Building a table takes about `2^w` operations per window of `w` bits, and each
+/// multiplication takes one operation per window. Returns the `w` minimizing the sum.
+fn optimal_window_size(bits: usize, number_of_multiplications: usize) -> usize {
+    let cost = |w: usize| bits.div_ceil(w) * ((1 << w) + number_of_multiplications);
+    (1..=MAX_WINDOW_SIZE.min(bits))
+        .min_by_key(|&w| cost(w))
+        .unwrap_or(1)
+}
+
+/// Returns the number of bits of the largest scalar.
+fn scalar_bits<G: CyclicGroup>() -> usize {
+    G::Scalar::representative_bits().max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::curves::bn254::curve::G1Point;
+    use crate::math::curves::bn254::field_extension::Fr;
+    use crate::math::curves::supersingular::Curve103;
+    use crate::math::elliptic_curve::EllipticCurveElement;
+    use crate::math::field::Field;
+    use crate::math::field_element::FieldElement;
+
+    #[test]
+    fn matches_scalar_multiplication_for_every_scalar_of_curve_103() {
+        let g = EllipticCurveElement::<Curve103>::generator();
+        for window_size in 1..=5 {
+            let table = FixedBaseTable::with_window_size(&g, window_size);
+            for k in 0..13 {
+                let k = FieldElement::new(k);
+                assert_eq!(table.operate_with_scalar(&k), g.operate_with_scalar(&k));
+                assert_eq!(
+                    table.operate_with_secret_scalar(&k),
+                    g.operate_with_scalar(&k)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_scalar_multiplication_over_bn254() {
+        let g = G1Point::generator();
+        let table = FixedBaseTable::new(&g, 10);
+        for _ in 0..3 {
+            let k = Fr::random();
+            assert_eq!(table.operate_with_scalar(&k), g.operate_with_scalar(&k));
+            assert_eq!(
+                table.operate_with_secret_scalar(&k),
+                g.operate_with_scalar(&k)
+            );
+        }
+        assert_eq!(table.operate_with_scalar(&-Fr::one()), -&g);
+        assert_eq!(table.operate_with_secret_scalar(&-Fr::one()), -g);
+    }
+
+    #[test]
+    fn window_size_grows_with_the_number_of_multiplications() {
+        assert_eq!(optimal_window_size(254, 1), 2);
+        assert!(optimal_window_size(254, 100) < optimal_window_size(254, 10000));
+        assert!(optimal_window_size(254, 1 << 30) <= MAX_WINDOW_SIZE);
+    }
+
+    #[test]
+    fn window_size_is_at_most_the_number_of_bits() {
+        assert_eq!(optimal_window_size(3, 1000), 3);
+    }
+}
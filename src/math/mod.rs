@@ -1,6 +1,16 @@
+pub mod cubic_extension;
+pub mod curves;
 pub mod cyclic_group;
 pub mod elliptic_curve;
+pub mod field;
 pub mod field_element;
 pub mod field_extension_element;
+pub mod fixed_base;
+pub mod montgomery_field_element;
 pub mod msm;
+pub mod pairing;
 pub mod polynomial;
+pub mod quadratic_extension;
+pub mod scalar_multiplication;
+pub mod short_weierstrass;
+pub mod unsigned_integer;
@@ -0,0 +1,669 @@
+use super::cyclic_group::{CyclicGroup, Group, PairingEngine};
+use super::field::{conditional_swap_u64, ConditionalSwap, Field, FrobeniusField, PrimeField};
+use super::field_element::FieldElementError;
+use super::unsigned_integer::{IsUnsignedInteger, UnsignedInteger};
+use rand::prelude::*;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops;
+
+/// Configuration of a prime field with a multi-limb modulus.
+/// The modulus has to be an odd prime.
+pub trait IsModulus<const NUM_LIMBS: usize>:
+    Debug + Clone + Copy + PartialEq + Eq + Send + Sync
+{
+    const MODULUS: UnsignedInteger<NUM_LIMBS>;
+}
+
+/// Represents an element in Fp for primes `p` of up to `64 * NUM_LIMBS` bits.
+/// Values are stored in Montgomery form: the element `a` is represented
+/// by `a * R mod p`, where `R = 2^(64 * NUM_LIMBS)`. This allows to multiply
+/// without ever dividing by `p`.
+/// See "Handbook of Applied Cryptography", section 14.3.2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MontgomeryFieldElement<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> {
+    value: UnsignedInteger<NUM_LIMBS>,
+    phantom: PhantomData<M>,
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> MontgomeryFieldElement<M, NUM_LIMBS> {
+    /// R^2 mod p, used to move values into Montgomery form.
+    const R2: UnsignedInteger<NUM_LIMBS> = compute_r2_parameter(&M::MODULUS);
+    /// -p^{-1} mod 2^64
+    const MU: u64 = compute_mu_parameter(&M::MODULUS);
+
+    /// Creates a new field element with `value` modulo the order of the field
+    pub fn new(value: UnsignedInteger<NUM_LIMBS>) -> Self {
+        // value * R^2 / R = value * R mod p
+        Self {
+            value: cios(&value, &Self::R2, &M::MODULUS, Self::MU),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn from_u64(value: u64) -> Self {
+        Self::new(UnsignedInteger::from_u64(value))
+    }
+
+    /// Creates a new field element from an hexadecimal string.
+    pub fn from_hex(hex: &str) -> Self {
+        Self::new(UnsignedInteger::from_hex(hex))
+    }
+
+    /// Returns the order of the field.
+    pub fn modulus() -> UnsignedInteger<NUM_LIMBS> {
+        M::MODULUS
+    }
+
+    /// Returns a representative for a field element in the range [0, p).
+    pub fn representative(&self) -> UnsignedInteger<NUM_LIMBS> {
+        // value * 1 / R = a mod p
+        cios(&self.value, &UnsignedInteger::one(), &M::MODULUS, Self::MU)
+    }
+
+    /// Returns a random element from the field.
+    /// Random limbs are sampled until a non-zero value smaller than the modulus is found.
+    pub fn random() -> Self {
+        let bits = M::MODULUS.bits();
+        let mut rng = rand::thread_rng();
+        loop {
+            let mut limbs = [0u64; NUM_LIMBS];
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                let bits_in_limb = bits.saturating_sub(64 * i).min(64);
+                if bits_in_limb > 0 {
+                    *limb = rng.gen::<u64>() >> (64 - bits_in_limb);
+                }
+            }
+            let candidate = UnsignedInteger::from_limbs(limbs);
+            if !candidate.is_zero() && candidate < M::MODULUS {
+                return Self::new(candidate);
+            }
+        }
+    }
+
+    /// Returns `self` to the power of `exponent` using
+    /// right-to-left binary method for modular exponentiation.
+    pub fn pow<T: IsUnsignedInteger>(self, mut exponent: T) -> Self {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let mut result = Self::from_u64(1);
+        let mut base = self;
+
+        while exponent > zero {
+            // exponent % 2 == 1
+            if exponent & one == one {
+                result = result * base;
+            }
+            // exponent = exponent / 2
+            exponent = exponent >> 1;
+            base = base * base;
+        }
+        result
+    }
+
+    /// Computes the inverse of the element `self`.
+    /// Based on Fermat's little theorem.
+    pub fn inv(self) -> Result<Self, FieldElementError> {
+        if self.value.is_zero() {
+            Err(FieldElementError::DivisionByZero)
+        } else {
+            Ok(self.pow(M::MODULUS - UnsignedInteger::from_u64(2)))
+        }
+    }
+}
+
+/// Computes R^2 mod p by doubling 1 modulo p, 2 * 64 * NUM_LIMBS times.
+const fn compute_r2_parameter<const NUM_LIMBS: usize>(
+    modulus: &UnsignedInteger<NUM_LIMBS>,
+) -> UnsignedInteger<NUM_LIMBS> {
+    let mut result = UnsignedInteger::one();
+    let mut i = 0;
+    while i < 2 * 64 * NUM_LIMBS {
+        let (doubled, overflow) = UnsignedInteger::add(&result, &result);
+        result = doubled;
+        if overflow || !matches!(UnsignedInteger::const_cmp(&result, modulus), Ordering::Less) {
+            result = UnsignedInteger::sub(&result, modulus).0;
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Computes -p^{-1} mod 2^64 with Newton's iteration.
+/// Each step doubles the number of correct bits of the inverse.
+const fn compute_mu_parameter<const NUM_LIMBS: usize>(modulus: &UnsignedInteger<NUM_LIMBS>) -> u64 {
+    let p0 = modulus.limbs[0];
+    let mut inverse: u64 = 1;
+    let mut i = 0;
+    while i < 6 {
+        inverse = inverse.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inverse)));
+        i += 1;
+    }
+    inverse.wrapping_neg()
+}
+
+/// Computes `a * b / R mod p` with the Coarsely Integrated Operand Scanning method.
+/// See "Analyzing and Comparing Montgomery Multiplication Algorithms",
+/// Koç, Acar and Kaliski, section 5.
+/// `a * b` must be smaller than `p * R`.
+fn cios<const NUM_LIMBS: usize>(
+    a: &UnsignedInteger<NUM_LIMBS>,
+    b: &UnsignedInteger<NUM_LIMBS>,
+    modulus: &UnsignedInteger<NUM_LIMBS>,
+    mu: u64,
+) -> UnsignedInteger<NUM_LIMBS> {
+    // The accumulator has NUM_LIMBS + 2 words: `t` plus `t_extra` and `t_extra_high`.
+    let mut t = [0u64; NUM_LIMBS];
+    let mut t_extra: u64 = 0;
+    let mut t_extra_high: u64;
+
+    for i in 0..NUM_LIMBS {
+        // t = t + a * b[i]
+        let mut carry: u128 = 0;
+        for (t_j, a_j) in t.iter_mut().zip(a.limbs) {
+            let sum = (*t_j as u128) + (a_j as u128) * (b.limbs[i] as u128) + carry;
+            *t_j = sum as u64;
+            carry = sum >> 64;
+        }
+        let sum = (t_extra as u128) + carry;
+        t_extra = sum as u64;
+        t_extra_high = (sum >> 64) as u64;
+
+        // t = (t + m * p) / 2^64, where m makes the lowest word vanish
+        let m = t[0].wrapping_mul(mu);
+        let sum = (t[0] as u128) + (m as u128) * (modulus.limbs[0] as u128);
+        let mut carry = sum >> 64;
+        for j in 1..NUM_LIMBS {
+            let sum = (t[j] as u128) + (m as u128) * (modulus.limbs[j] as u128) + carry;
+            t[j - 1] = sum as u64;
+            carry = sum >> 64;
+        }
+        let sum = (t_extra as u128) + carry;
+        t[NUM_LIMBS - 1] = sum as u64;
+        t_extra = t_extra_high + ((sum >> 64) as u64);
+    }
+
+    let result = UnsignedInteger::from_limbs(t);
+    if t_extra != 0 || result >= *modulus {
+        UnsignedInteger::sub(&result, modulus).0
+    } else {
+        result
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::Add<MontgomeryFieldElement<M, NUM_LIMBS>>
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Output = MontgomeryFieldElement<M, NUM_LIMBS>;
+
+    fn add(self, a_field_element: Self) -> Self {
+        let (sum, overflow) = UnsignedInteger::add(&self.value, &a_field_element.value);
+        let value = if overflow || sum >= M::MODULUS {
+            UnsignedInteger::sub(&sum, &M::MODULUS).0
+        } else {
+            sum
+        };
+        Self {
+            value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::AddAssign
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::Neg
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Output = MontgomeryFieldElement<M, NUM_LIMBS>;
+
+    fn neg(self) -> Self {
+        if self.value.is_zero() {
+            self
+        } else {
+            Self {
+                value: UnsignedInteger::sub(&M::MODULUS, &self.value).0,
+                phantom: PhantomData,
+            }
+        }
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::Sub<MontgomeryFieldElement<M, NUM_LIMBS>>
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Output = MontgomeryFieldElement<M, NUM_LIMBS>;
+
+    fn sub(self, substrahend: Self) -> Self {
+        let (difference, underflow) = UnsignedInteger::sub(&self.value, &substrahend.value);
+        let value = if underflow {
+            UnsignedInteger::add(&difference, &M::MODULUS).0
+        } else {
+            difference
+        };
+        Self {
+            value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::Mul
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Output = MontgomeryFieldElement<M, NUM_LIMBS>;
+
+    fn mul(self, a_field_element: Self) -> Self {
+        Self {
+            value: cios(&self.value, &a_field_element.value, &M::MODULUS, Self::MU),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ops::Div
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Output = MontgomeryFieldElement<M, NUM_LIMBS>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, dividend: Self) -> Self {
+        self * dividend.inv().unwrap()
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> Field
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    fn zero() -> Self {
+        Self::from_u64(0)
+    }
+
+    fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        MontgomeryFieldElement::from_u64(value)
+    }
+
+    fn inv(&self) -> Result<Self, FieldElementError> {
+        MontgomeryFieldElement::inv(*self)
+    }
+
+    fn random() -> Self {
+        MontgomeryFieldElement::random()
+    }
+
+    /// Encodes the representative in big-endian order using
+    /// the minimum number of bytes needed for the modulus.
+    fn to_bytes(&self) -> Vec<u8> {
+        let byte_length = M::MODULUS.bits().div_ceil(8);
+        let bytes: Vec<u8> = self
+            .representative()
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect();
+        bytes[bytes.len() - byte_length..].to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError> {
+        if bytes.len() != M::MODULUS.bits().div_ceil(8) {
+            return Err(FieldElementError::InvalidLength);
+        }
+        let mut limbs = [0u64; NUM_LIMBS];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            limbs[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        let value = UnsignedInteger::from_limbs(limbs);
+        if value >= M::MODULUS {
+            return Err(FieldElementError::OutOfRangeValue);
+        }
+        Ok(Self::new(value))
+    }
+
+    fn pow<T: IsUnsignedInteger>(&self, exponent: T) -> Self {
+        MontgomeryFieldElement::pow(*self, exponent)
+    }
+}
+
+/// Elements of a prime field are fixed by the Frobenius map.
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> ConditionalSwap
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        for (x, y) in a.value.limbs.iter_mut().zip(b.value.limbs.iter_mut()) {
+            conditional_swap_u64(x, y, mask);
+        }
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> FrobeniusField
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    fn frobenius(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> PrimeField
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type RepresentativeType = UnsignedInteger<NUM_LIMBS>;
+
+    fn representative(&self) -> UnsignedInteger<NUM_LIMBS> {
+        MontgomeryFieldElement::representative(self)
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> Group
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    fn neutral_element() -> Self {
+        Self::from_u64(0)
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn inverse(&self) -> Self {
+        -*self
+    }
+}
+
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> CyclicGroup
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Scalar = Self;
+
+    fn generator() -> Self {
+        Self::from_u64(1)
+    }
+
+    /// The whole field has the order of the scalar field.
+    fn is_in_subgroup(&self) -> bool {
+        true
+    }
+}
+
+/// Symmetric pairing over the raw values, without any hiding.
+/// The target group is the field under addition, so it is only useful for testing.
+impl<M: IsModulus<NUM_LIMBS>, const NUM_LIMBS: usize> PairingEngine
+    for MontgomeryFieldElement<M, NUM_LIMBS>
+{
+    type Scalar = Self;
+    type G1 = Self;
+    type G2 = Self;
+    type GT = Self;
+    type G1Prepared = Self;
+    type G2Prepared = Self;
+
+    fn pairing(p: &Self, q: &Self) -> Self {
+        *p * *q
+    }
+
+    fn prepare_g1(p: &Self) -> Self {
+        *p
+    }
+
+    fn prepare_g2(q: &Self) -> Self {
+        *q
+    }
+
+    fn multi_pairing_prepared(pairs: &[(&Self, &Self)]) -> Self {
+        pairs
+            .iter()
+            .fold(Self::neutral_element(), |product, (p, q)| {
+                product.operate_with(&Self::pairing(p, q))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::unsigned_integer::{U128, U256, U384};
+
+    // Base field of the BN254 elliptic curve.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Modulus254;
+    impl IsModulus<4> for Modulus254 {
+        const MODULUS: U256 =
+            U256::from_hex("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
+    }
+    type FE254 = MontgomeryFieldElement<Modulus254, 4>;
+
+    // Base field of the BLS12-381 elliptic curve.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Modulus381;
+    impl IsModulus<6> for Modulus381 {
+        const MODULUS: U384 = U384::from_hex("0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+    }
+    type FE381 = MontgomeryFieldElement<Modulus381, 6>;
+
+    // The Mersenne prime 2^127 - 1.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Modulus127;
+    impl IsModulus<2> for Modulus127 {
+        const MODULUS: U128 = U128::from_hex("0x7fffffffffffffffffffffffffffffff");
+    }
+    type FE127 = MontgomeryFieldElement<Modulus127, 2>;
+
+    // A prime with no spare bit in its most significant limb: 2^64 - 59.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Modulus64;
+    impl IsModulus<1> for Modulus64 {
+        const MODULUS: UnsignedInteger<1> = UnsignedInteger::from_limbs([u64::MAX - 58]);
+    }
+    type FE64 = MontgomeryFieldElement<Modulus64, 1>;
+
+    #[test]
+    fn two_plus_one_is_three() {
+        assert_eq!(FE254::from_u64(2) + FE254::from_u64(1), FE254::from_u64(3));
+    }
+
+    #[test]
+    fn representative_of_new_element_is_its_value() {
+        let value = U256::from_hex("0x1234567890abcdef1234567890abcdef1234567890abcdef");
+        assert_eq!(FE254::new(value).representative(), value);
+    }
+
+    #[test]
+    fn new_reduces_values_bigger_than_the_modulus() {
+        let p_plus_5 = Modulus254::MODULUS + U256::from_u64(5);
+        assert_eq!(FE254::new(p_plus_5), FE254::from_u64(5));
+    }
+
+    #[test]
+    fn max_order_plus_1_is_0() {
+        let p_minus_1 = Modulus254::MODULUS - U256::one();
+        assert_eq!(
+            FE254::new(p_minus_1) + FE254::from_u64(1),
+            FE254::from_u64(0)
+        );
+    }
+
+    #[test]
+    fn zero_minus_1_is_order_minus_1() {
+        let p_minus_1 = Modulus254::MODULUS - U256::one();
+        assert_eq!(
+            (FE254::from_u64(0) - FE254::from_u64(1)).representative(),
+            p_minus_1
+        );
+    }
+
+    #[test]
+    fn neg_zero_is_zero() {
+        assert_eq!(-FE254::from_u64(0), FE254::from_u64(0));
+    }
+
+    #[test]
+    fn mul_2_3_is_6() {
+        assert_eq!(FE254::from_u64(2) * FE254::from_u64(3), FE254::from_u64(6));
+    }
+
+    #[test]
+    fn mul_order_minus_1() {
+        let minus_one = -FE254::from_u64(1);
+        assert_eq!(minus_one * minus_one, FE254::from_u64(1));
+    }
+
+    #[test]
+    fn mul_of_large_values_in_254_bit_field() {
+        // Computed with: (2^200 + 3) * (2^190 + 7) mod p
+        let a = FE254::new(U256::one() << 200) + FE254::from_u64(3);
+        let b = FE254::new(U256::one() << 190) + FE254::from_u64(7);
+        let expected =
+            FE254::from_hex("0x10e99ffd3efe29885c225d8d93819b03e5afcbe466361c9c8f66049ee6c4587f");
+        assert_eq!(a * b, expected);
+    }
+
+    #[test]
+    fn two_to_the_127_is_1_in_mersenne_field() {
+        assert_eq!(FE127::from_u64(2).pow(127u64), FE127::from_u64(1));
+    }
+
+    #[test]
+    fn pow_p_minus_1_is_1_in_254_bit_field() {
+        let p_minus_1 = Modulus254::MODULUS - U256::one();
+        assert_eq!(FE254::from_u64(7).pow(p_minus_1), FE254::from_u64(1));
+    }
+
+    #[test]
+    fn pow_p_minus_1_is_1_in_381_bit_field() {
+        let p_minus_1 = Modulus381::MODULUS - U384::one();
+        assert_eq!(FE381::from_u64(5).pow(p_minus_1), FE381::from_u64(1));
+    }
+
+    #[test]
+    fn pow_p_minus_1_is_1_when_modulus_has_no_spare_bits() {
+        let p_minus_1 = Modulus64::MODULUS - UnsignedInteger::one();
+        let a = FE64::new(UnsignedInteger::from_u64(u64::MAX - 100));
+        assert_eq!(a.pow(p_minus_1), FE64::from_u64(1));
+    }
+
+    #[test]
+    fn mul_when_modulus_has_no_spare_bits_matches_u128_arithmetic() {
+        let p = (u64::MAX - 58) as u128;
+        let a = u64::MAX - 100;
+        let b = u64::MAX - 200;
+        let expected = ((a as u128) * (b as u128)) % p;
+        let product = FE64::from_u64(a) * FE64::from_u64(b);
+        assert_eq!(product.representative().limbs[0] as u128, expected);
+    }
+
+    #[test]
+    fn inv_0_error() {
+        assert_eq!(
+            FE254::from_u64(0).inv().unwrap_err(),
+            FieldElementError::DivisionByZero
+        );
+    }
+
+    #[test]
+    fn inv_of_random_element_in_381_bit_field() {
+        let a = FE381::random();
+        assert_eq!(a * a.inv().unwrap(), FE381::from_u64(1));
+    }
+
+    #[test]
+    fn div_4_3() {
+        assert_eq!(
+            FE254::from_u64(4) / FE254::from_u64(3) * FE254::from_u64(3),
+            FE254::from_u64(4)
+        );
+    }
+
+    #[test]
+    fn random_element_is_smaller_than_modulus() {
+        for _ in 0..10 {
+            assert!(FE381::random().representative() < Modulus381::MODULUS);
+        }
+    }
+
+    #[test]
+    fn distributivity_holds_for_random_elements() {
+        let a = FE254::random();
+        let b = FE254::random();
+        let c = FE254::random();
+        assert_eq!(a * (b + c), a * b + a * c);
+    }
+
+    #[test]
+    fn to_bytes_is_32_bytes_big_endian_in_254_bit_field() {
+        let mut expected = vec![0u8; 32];
+        expected[30] = 1;
+        expected[31] = 2;
+        assert_eq!(Field::to_bytes(&FE254::from_u64(258)), expected);
+    }
+
+    #[test]
+    fn from_bytes_inverts_to_bytes_in_254_bit_field() {
+        let a =
+            FE254::from_hex("0x2a6f0d2c2d4b4e5c1f0e3d7b8a9c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c");
+        assert_eq!(FE254::from_bytes(&Field::to_bytes(&a)), Ok(a));
+    }
+
+    #[test]
+    fn from_bytes_rejects_the_modulus_and_wrong_lengths() {
+        let mut modulus = Field::to_bytes(&FE254::from_u64(0));
+        for (byte, limb_byte) in modulus.iter_mut().zip(
+            Modulus254::MODULUS
+                .limbs
+                .iter()
+                .rev()
+                .flat_map(|limb| limb.to_be_bytes()),
+        ) {
+            *byte = limb_byte;
+        }
+        assert_eq!(
+            FE254::from_bytes(&modulus),
+            Err(FieldElementError::OutOfRangeValue)
+        );
+        assert_eq!(
+            FE254::from_bytes(&modulus[1..]),
+            Err(FieldElementError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn montgomery_field_element_as_group_element_multiplication_by_scalar_works() {
+        let a = FE254::from_u64(3);
+        assert_eq!(a.operate_with_self(12u64), FE254::from_u64(36));
+    }
+
+    #[test]
+    fn sqrt_of_a_square_is_one_of_its_roots() {
+        use crate::math::curves::bn254::field_extension::{Fp, Fr};
+        // p = 3 mod 4 for Fp, and r - 1 is divisible by 2^28 for Fr.
+        for _ in 0..5 {
+            let a = Fp::random();
+            let root = PrimeField::sqrt(&(a * a)).unwrap();
+            assert!(root == a || root == -a);
+
+            let b = Fr::random();
+            let root = PrimeField::sqrt(&(b * b)).unwrap();
+            assert!(root == b || root == -b);
+        }
+    }
+
+    #[test]
+    fn non_squares_have_no_square_root() {
+        use crate::math::curves::bn254::field_extension::Fr;
+        // 5 is not a square modulo the order of BN254.
+        let five = Fr::from_u64(5);
+        assert_eq!(five.legendre(), -1);
+        assert_eq!(PrimeField::sqrt(&five), None);
+        assert_eq!(PrimeField::sqrt(&(five * Fr::from_u64(4))), None);
+    }
+}
@@ -1,89 +1,198 @@
-use crate::config::ORDER_R;
-use crate::math::cyclic_group::CyclicBilinearGroup;
-use crate::math::field_element::FieldElement;
+use crate::math::cyclic_group::CyclicGroup;
+use crate::math::field::PrimeField;
+use crate::math::unsigned_integer::{bit_length, window_value};
+use crate::parallel;
 
-type FE = FieldElement<ORDER_R>;
+/// Below this number of terms the bucket method does not pay off
+/// and `msm` computes each term separately.
+const PIPPENGER_THRESHOLD: usize = 8;
 
 /// This function computes the multiscalar multiplication (MSM).
 ///
 /// Assume a group G of order r is given.
 /// Let `hidings = [g_1, ..., g_n]` be a tuple of group points in G and
-/// let `cs = [k_1, ..., k_n]` be a tuple of scalars in the Galois field GF(r).
+/// let `cs = [k_1, ..., k_n]` be a tuple of scalars in the Galois field GF(r),
+/// which is the scalar field of G.
 ///
 /// Then, with additive notation, `msm(cs, hidings)` computes k_1 * g_1 + .... + k_n * g_n.
 ///
 /// If `hidings` and `cs` are empty, then `msm` returns the zero element of the group.
 ///
 /// Panics if `cs` and `hidings` have different lengths.
-pub fn msm<T>(cs: &[FE], hidings: &[T]) -> T
-where
-    T: CyclicBilinearGroup,
-{
+pub fn msm<T: CyclicGroup>(cs: &[T::Scalar], hidings: &[T]) -> T {
     assert_eq!(
         cs.len(),
         hidings.len(),
         "Slices `cs` and `hidings` must be of the same length to compute `msm`."
     );
+    if cs.len() < PIPPENGER_THRESHOLD {
+        naive_msm(cs, hidings)
+    } else {
+        pippenger_msm(cs, hidings, window_size(cs.len()))
+    }
+}
+
+/// Computes the MSM term by term, with one scalar multiplication per term.
+fn naive_msm<T: CyclicGroup>(cs: &[T::Scalar], hidings: &[T]) -> T {
     cs.iter()
         .zip(hidings.iter())
-        .map(|(&c, h)| h.operate_with_self(c.representative()))
+        .map(|(c, h)| h.operate_with_scalar(c))
         .reduce(|acc, x| acc.operate_with(&x))
         .unwrap_or_else(T::neutral_element)
 }
 
+/// Computes the MSM with the bucket method of Pippenger.
+///
+/// The scalars are split in windows of `window_size` bits. For each window, every
+/// point is added to the bucket indexed by the bits of its scalar in that window,
+/// and the buckets are combined as sum_j j * B_j with a running sum.
+/// The windows are then put together, from the most significant one down,
+/// doubling `window_size` times between them.
+/// Windows are independent, so they are the unit of work split across threads.
+/// See "Speeding Up Elliptic Curve Scalar Multiplication without Precomputation",
+/// section 4, or the survey "Pippenger's Multiproduct and Multiexponentiation Algorithms".
+fn pippenger_msm<T: CyclicGroup>(cs: &[T::Scalar], hidings: &[T], window_size: usize) -> T {
+    let representatives: Vec<_> = cs.iter().map(|c| c.representative()).collect();
+    let number_of_bits = representatives
+        .iter()
+        .map(|&k| bit_length(k))
+        .max()
+        .unwrap_or(0);
+    let window_size = window_size.min(number_of_bits.max(1));
+    let windows: Vec<usize> = (0..number_of_bits.div_ceil(window_size)).collect();
+
+    let window_sums = parallel::map(&windows, |window| {
+        // Bucket `j` holds the sum of the points whose window is equal to `j + 1`.
+        let mut buckets = vec![T::neutral_element(); (1 << window_size) - 1];
+        for (&k, h) in representatives.iter().zip(hidings.iter()) {
+            let index = window_value(k, window * window_size, window_size);
+            if index != 0 {
+                buckets[index - 1] = buckets[index - 1].operate_with(h);
+            }
+        }
+
+        // The running sum adds bucket `j` exactly `j + 1` times to `window_sum`.
+        let mut running_sum = T::neutral_element();
+        let mut window_sum = T::neutral_element();
+        for bucket in buckets.iter().rev() {
+            running_sum = running_sum.operate_with(bucket);
+            window_sum = window_sum.operate_with(&running_sum);
+        }
+        window_sum
+    });
+
+    let mut result = T::neutral_element();
+    for window_sum in window_sums.iter().rev() {
+        for _ in 0..window_size {
+            result = result.operate_with(&result);
+        }
+        result = result.operate_with(window_sum);
+    }
+    result
+}
+
+/// Returns the window size in bits that minimizes the number of group
+/// operations of the bucket method for `number_of_terms` terms,
+/// which is roughly the natural logarithm of `number_of_terms`.
+fn window_size(number_of_terms: usize) -> usize {
+    if number_of_terms < 32 {
+        3
+    } else {
+        // ln(n) = log2(n) * ln(2), with ln(2) ~ 0.69
+        (number_of_terms.ilog2() as usize * 69 / 100) + 2
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::elliptic_curve::EllipticCurveElement;
+    use crate::config::{DefaultCurve, ORDER_R};
+    use crate::math::curves::bn254::field_extension::Fr;
+    use crate::math::curves::supersingular::Curve103;
+    use crate::math::cyclic_group::Group;
+    use crate::math::elliptic_curve;
+    use crate::math::field::Field;
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<ORDER_R>;
+    type EllipticCurveElement = elliptic_curve::EllipticCurveElement<DefaultCurve>;
+
+    // Checks `msm` together with the naive and the bucket methods on their own,
+    // so that the bucket method is also exercised below the threshold.
+    fn assert_msm_is<T: CyclicGroup>(cs: &[T::Scalar], hidings: &[T], expected: T) {
+        assert_eq!(msm(cs, hidings), expected);
+        assert_eq!(naive_msm(cs, hidings), expected);
+        for window_size in 1..=4 {
+            assert_eq!(pippenger_msm(cs, hidings, window_size), expected);
+        }
+    }
+
+    fn bucket_method_matches_naive_msm<T: CyclicGroup>(number_of_terms: usize) {
+        let cs: Vec<T::Scalar> = (0..number_of_terms).map(|_| T::Scalar::random()).collect();
+        let hidings: Vec<T> = (0..number_of_terms)
+            .map(|_| T::generator().operate_with_scalar(&T::Scalar::random()))
+            .collect();
+        assert_eq!(msm(&cs, &hidings), naive_msm(&cs, &hidings));
+    }
 
     #[test]
     fn msm_11_is_1_over_elliptic_curves() {
         let c = [FE::new(1)];
         let hiding = [EllipticCurveElement::generator()];
-        assert_eq!(msm(&c, &hiding), EllipticCurveElement::generator());
+        assert_msm_is(&c, &hiding, EllipticCurveElement::generator());
     }
 
     #[test]
     fn msm_23_is_6_over_field_elements() {
         let c = [FE::new(3)];
         let hiding = [FE::new(2)];
-        assert_eq!(msm(&c, &hiding), FE::new(6));
+        assert_msm_is(&c, &hiding, FE::new(6));
     }
 
     #[test]
     fn msm_23_is_6_over_elliptic_curves() {
         let c = [FE::new(3)];
         let g = EllipticCurveElement::generator();
-        let hiding = [g.operate_with_self(2)];
-        assert_eq!(msm(&c, &hiding), g.operate_with_self(6));
+        let hiding = [g.operate_with_self(2u64)];
+        assert_msm_is(&c, &hiding, g.operate_with_self(6u64));
     }
 
     #[test]
     fn msm_with_c_2_3_hiding_3_4_is_18_over_field_elements() {
         let c = [FE::new(2), FE::new(3)];
         let hiding = [FE::new(3), FE::new(4)];
-        assert_eq!(msm(&c, &hiding), FE::new(18));
+        assert_msm_is(&c, &hiding, FE::new(18));
     }
 
     #[test]
     fn msm_with_c_2_3_hiding_3_4_is_18_over_elliptic_curves() {
         let c = [FE::new(2), FE::new(3)];
         let g = EllipticCurveElement::generator();
-        let hiding = [g.operate_with_self(3), g.operate_with_self(4)];
-        assert_eq!(msm(&c, &hiding), g.operate_with_self(18));
+        let hiding = [g.operate_with_self(3u64), g.operate_with_self(4u64)];
+        assert_msm_is(&c, &hiding, g.operate_with_self(18u64));
     }
 
     #[test]
     fn msm_with_empty_input_over_field_elements() {
-        let c = [];
+        let c: [FE; 0] = [];
         let hiding: [FE; 0] = [];
-        assert_eq!(msm(&c, &hiding), FE::new(0));
+        assert_msm_is(&c, &hiding, FE::new(0));
     }
 
     #[test]
     fn msm_with_empty_c_is_none_over_elliptic_curves() {
-        let c = [];
+        let c: [FE; 0] = [];
         let hiding: [EllipticCurveElement; 0] = [];
-        assert_eq!(msm(&c, &hiding), EllipticCurveElement::neutral_element());
+        assert_msm_is(&c, &hiding, EllipticCurveElement::neutral_element());
+    }
+
+    #[test]
+    fn bucket_method_matches_naive_msm_over_elliptic_curves() {
+        bucket_method_matches_naive_msm::<elliptic_curve::EllipticCurveElement<Curve103>>(40);
+    }
+
+    #[test]
+    fn bucket_method_matches_naive_msm_over_a_254_bit_field() {
+        bucket_method_matches_naive_msm::<Fr>(100);
     }
 }
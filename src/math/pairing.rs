@@ -0,0 +1,166 @@
+//! Pairings over the curves of `elliptic_curve`, with a choice of variant so that
+//! they can be compared. Every variant takes two points of the subgroup of order r
+//! over the base field and applies the distortion map to the second one, like
+//! `PairingEngine::pairing`, which is the reduced Tate pairing.
+//! See "Pairings for beginners" from Craig Costello, chapter 5.
+use super::elliptic_curve::{CurveParams, EllipticCurveElement};
+use super::field::Field;
+use super::field_extension_element::FieldExtensionElement;
+
+#[allow(clippy::upper_case_acronyms)]
+type FEE<C> = FieldExtensionElement<<C as CurveParams>::BaseField>;
+
+/// Variants of the pairing e(P, Q), where Q stands for the image of the second
+/// argument under the distortion map and f_{n,R} for the function of Miller's
+/// algorithm over R with n as loop count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairingVariant {
+    /// -f_{r,P}(Q) / f_{r,Q}(P), with no final exponentiation.
+    Weil,
+    /// f_{r,P}(Q)^((p^2 - 1) / r).
+    ReducedTate,
+    /// f_{T,Q}(P)^((p^2 - 1) / r), where T is `CurveParams::ATE_LOOP_COUNT`.
+    /// The Frobenius map acts on Q as the multiplication by p.
+    Ate,
+}
+
+/// Computes the pairing `variant` between `p` and `q`.
+/// Returns one if any of them is the point at infinity.
+pub fn pairing<C: CurveParams>(
+    variant: PairingVariant,
+    p: &EllipticCurveElement<C>,
+    q: &EllipticCurveElement<C>,
+) -> FEE<C> {
+    let f = miller_output(variant, p, q);
+    match variant {
+        PairingVariant::Weil => f,
+        PairingVariant::ReducedTate | PairingVariant::Ate => final_exponentiation::<C>(&f),
+    }
+}
+
+/// Returns the output of Miller's algorithm for the pairing `variant` between `p`
+/// and `q`, before the final exponentiation. For the Weil pairing, which has no
+/// final exponentiation, it is the pairing itself.
+/// Returns one if any of them is the point at infinity.
+pub fn miller_output<C: CurveParams>(
+    variant: PairingVariant,
+    p: &EllipticCurveElement<C>,
+    q: &EllipticCurveElement<C>,
+) -> FEE<C> {
+    if p.is_neutral_element() || q.is_neutral_element() {
+        return FEE::<C>::one();
+    }
+    let q = EllipticCurveElement::distorsion_map(q);
+    match variant {
+        PairingVariant::Weil => EllipticCurveElement::weil_pairing(p, &q),
+        PairingVariant::ReducedTate => EllipticCurveElement::miller(p, &q),
+        PairingVariant::Ate => {
+            EllipticCurveElement::miller_with_loop_count(&q, p, C::ATE_LOOP_COUNT)
+        }
+    }
+}
+
+/// Raises the output of Miller's algorithm to the power (p^2 - 1) / r.
+pub fn final_exponentiation<C: CurveParams>(f: &FEE<C>) -> FEE<C> {
+    EllipticCurveElement::<C>::final_exponentiation(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DefaultCurve, ORDER_R};
+    use crate::math::cyclic_group::{CyclicGroup, Group, PairingEngine};
+
+    type EllipticCurveElement = super::EllipticCurveElement<DefaultCurve>;
+    #[allow(clippy::upper_case_acronyms)]
+    type FEE = super::FEE<DefaultCurve>;
+
+    const VARIANTS: [PairingVariant; 3] = [
+        PairingVariant::Weil,
+        PairingVariant::ReducedTate,
+        PairingVariant::Ate,
+    ];
+
+    #[test]
+    fn every_variant_is_bilinear() {
+        let g = EllipticCurveElement::generator();
+        for variant in VARIANTS {
+            let e = pairing(variant, &g, &g);
+            for a in 0..ORDER_R {
+                for b in 0..ORDER_R {
+                    assert_eq!(
+                        pairing(variant, &g.operate_with_self(a), &g.operate_with_self(b)),
+                        e.pow(a * b),
+                        "{variant:?} with a = {a} and b = {b}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_variant_is_linear_in_each_argument() {
+        let g = EllipticCurveElement::generator();
+        for variant in VARIANTS {
+            for a in 1..ORDER_R {
+                let p = g.operate_with_self(a);
+                let q = g.operate_with_self(ORDER_R - a);
+                let r = g.operate_with_self(2u64);
+                assert_eq!(
+                    pairing(variant, &p.operate_with(&q), &r),
+                    pairing(variant, &p, &r) * pairing(variant, &q, &r)
+                );
+                assert_eq!(
+                    pairing(variant, &r, &p.operate_with(&r)),
+                    pairing(variant, &r, &p) * pairing(variant, &r, &r)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_variant_is_non_degenerate() {
+        let g = EllipticCurveElement::generator();
+        for variant in VARIANTS {
+            let e = pairing(variant, &g, &g);
+            assert_ne!(e, FEE::one(), "{variant:?}");
+            assert_eq!(e.pow(ORDER_R), FEE::one(), "{variant:?}");
+        }
+    }
+
+    #[test]
+    fn reduced_tate_is_the_engine_pairing() {
+        let g = EllipticCurveElement::generator();
+        let p = g.operate_with_self(2u64);
+        let q = g.operate_with_self(3u64);
+        assert_eq!(
+            pairing(PairingVariant::ReducedTate, &p, &q),
+            EllipticCurveElement::pairing(&p, &q)
+        );
+        assert_eq!(
+            final_exponentiation::<DefaultCurve>(&miller_output(
+                PairingVariant::ReducedTate,
+                &p,
+                &q
+            )),
+            EllipticCurveElement::pairing(&p, &q)
+        );
+    }
+
+    #[test]
+    fn miller_output_is_not_yet_in_the_subgroup_of_order_r() {
+        let g = EllipticCurveElement::generator();
+        let f = miller_output(PairingVariant::ReducedTate, &g, &g.operate_with_self(2u64));
+        assert_ne!(f.pow(ORDER_R), FEE::one());
+    }
+
+    #[test]
+    fn pairing_with_the_point_at_infinity_is_one() {
+        let g = EllipticCurveElement::generator();
+        let infinity = EllipticCurveElement::neutral_element();
+        for variant in VARIANTS {
+            assert_eq!(pairing(variant, &g, &infinity), FEE::one());
+            assert_eq!(pairing(variant, &infinity, &g), FEE::one());
+        }
+    }
+}
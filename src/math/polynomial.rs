@@ -1,50 +1,47 @@
-use super::field_element::FieldElement;
+use super::field::Field;
 use std::ops;
 
 /// Represents the polynomial c_0 + c_1 * X + c_2 * X^2 + ... + c_n * X^n
 /// as a vector of coefficients `[c_0, c_1, ... , c_n]`
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Polynomial<const ORDER: u128> {
-    coefficients: Vec<FieldElement<ORDER>>,
+pub struct Polynomial<F: Field> {
+    coefficients: Vec<F>,
 }
 
-impl<const ORDER: u128> Polynomial<ORDER> {
+impl<F: Field> Polynomial<F> {
     /// Creates a new polynomial with the given coefficients
-    pub fn new(coefficients: Vec<FieldElement<ORDER>>) -> Self {
+    pub fn new(coefficients: Vec<F>) -> Self {
         // Removes trailing zero coefficients at the end
         let mut unpadded_coefficients = coefficients
             .into_iter()
             .rev()
-            .skip_while(|x| *x == FieldElement::new(0))
-            .collect::<Vec<FieldElement<ORDER>>>();
+            .skip_while(|x| *x == F::zero())
+            .collect::<Vec<F>>();
         unpadded_coefficients.reverse();
         Polynomial {
             coefficients: unpadded_coefficients,
         }
     }
 
-    pub fn new_monomial(coefficient: FieldElement<ORDER>, degree: usize) -> Self {
-        let mut coefficients = vec![FieldElement::new(0); degree];
+    pub fn new_monomial(coefficient: F, degree: usize) -> Self {
+        let mut coefficients = vec![F::zero(); degree];
         coefficients.push(coefficient);
         Self::new(coefficients)
     }
 
     pub fn zero() -> Self {
-        Self::new(Vec::<FieldElement<ORDER>>::new())
+        Self::new(Vec::<F>::new())
     }
 
-    pub fn interpolate(
-        xs: &[FieldElement<ORDER>],
-        ys: &[FieldElement<ORDER>],
-    ) -> Polynomial<ORDER> {
+    pub fn interpolate(xs: &[F], ys: &[F]) -> Polynomial<F> {
         let mut result = Polynomial::zero();
 
         for (i, y) in ys.iter().enumerate() {
-            let mut y_term = Polynomial::new(vec![*y]);
+            let mut y_term = Polynomial::new(vec![y.clone()]);
             for (j, x) in xs.iter().enumerate() {
                 if i != j {
-                    let denominator = Polynomial::new(vec![FieldElement::new(1) / (xs[i] - *x)]);
-                    let numerator = Polynomial::new(vec![-*x, FieldElement::new(1)]);
+                    let denominator = Polynomial::new(vec![F::one() / (xs[i].clone() - x.clone())]);
+                    let numerator = Polynomial::new(vec![-x.clone(), F::one()]);
                     y_term = y_term.mul_with_ref(&(numerator * denominator));
                 }
             }
@@ -53,13 +50,11 @@ impl<const ORDER: u128> Polynomial<ORDER> {
         result
     }
 
-    pub fn evaluate(&self, x: FieldElement<ORDER>) -> FieldElement<ORDER> {
+    pub fn evaluate(&self, x: F) -> F {
         self.coefficients
             .iter()
             .enumerate()
-            .fold(FieldElement::new(0), |acc, (i, &c)| {
-                acc + c * x.pow(i as u128)
-            })
+            .fold(F::zero(), |acc, (i, c)| acc + c.clone() * x.pow(i))
     }
 
     pub fn degree(&self) -> usize {
@@ -70,11 +65,11 @@ impl<const ORDER: u128> Polynomial<ORDER> {
         }
     }
 
-    pub fn leading_coefficient(&self) -> FieldElement<ORDER> {
+    pub fn leading_coefficient(&self) -> F {
         if let Some(coefficient) = self.coefficients.last() {
-            *coefficient
+            coefficient.clone()
         } else {
-            FieldElement::new(0)
+            F::zero()
         }
     }
 
@@ -82,24 +77,22 @@ impl<const ORDER: u128> Polynomial<ORDER> {
     /// \[c_0, c_1, c_2, ..., c_n\]
     /// that represents the polynomial
     /// c_0 + c_1 * X + c_2 * X^2 + ... + c_n * X^n
-    pub fn coefficients(&self) -> &[FieldElement<ORDER>] {
+    pub fn coefficients(&self) -> &[F] {
         &self.coefficients
     }
 
     /// Pads polynomial representations with minimum number of zeros to match lengths.
     fn pad_with_zero_coefficients(
-        pa: &Polynomial<ORDER>,
-        pb: &Polynomial<ORDER>,
-    ) -> (Polynomial<ORDER>, Polynomial<ORDER>) {
+        pa: &Polynomial<F>,
+        pb: &Polynomial<F>,
+    ) -> (Polynomial<F>, Polynomial<F>) {
         let mut pa = pa.clone();
         let mut pb = pb.clone();
 
         if pa.coefficients.len() > pb.coefficients.len() {
-            pb.coefficients
-                .resize(pa.coefficients.len(), FieldElement::new(0));
+            pb.coefficients.resize(pa.coefficients.len(), F::zero());
         } else {
-            pa.coefficients
-                .resize(pb.coefficients.len(), FieldElement::new(0));
+            pa.coefficients.resize(pb.coefficients.len(), F::zero());
         }
         (pa, pb)
     }
@@ -112,10 +105,10 @@ impl<const ORDER: u128> Polynomial<ORDER> {
             (Polynomial::zero(), self)
         } else {
             let mut n = self;
-            let mut q: Vec<FieldElement<ORDER>> = vec![FieldElement::new(0); n.degree() + 1];
+            let mut q: Vec<F> = vec![F::zero(); n.degree() + 1];
             while n != Polynomial::zero() && n.degree() >= dividend.degree() {
                 let new_coefficient = n.leading_coefficient() / dividend.leading_coefficient();
-                q[n.degree() - dividend.degree()] = new_coefficient;
+                q[n.degree() - dividend.degree()] = new_coefficient.clone();
                 let d = dividend.mul_with_ref(&Polynomial::new_monomial(
                     new_coefficient,
                     n.degree() - dividend.degree(),
@@ -131,16 +124,62 @@ impl<const ORDER: u128> Polynomial<ORDER> {
         quotient
     }
 
+    /// Computes the monic greatest common divisor `g` of `self` and `other`,
+    /// together with Bézout coefficients `s` and `t` such that
+    /// `s * self + t * other = g`, using the extended Euclidean algorithm.
+    ///
+    /// Output: (g, s, t)
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let one = Polynomial::new_monomial(F::one(), 0);
+        // Invariants: r = s * self + t * other, and the same for the previous ones.
+        let (mut previous_r, mut r) = (self.clone(), other.clone());
+        let (mut previous_s, mut s) = (one.clone(), Polynomial::zero());
+        let (mut previous_t, mut t) = (Polynomial::zero(), one);
+
+        while r != Polynomial::zero() {
+            let (quotient, remainder) = previous_r.long_division_with_remainder(&r);
+            previous_r = std::mem::replace(&mut r, remainder);
+            let next_s = previous_s - quotient.mul_with_ref(&s);
+            previous_s = std::mem::replace(&mut s, next_s);
+            let next_t = previous_t - quotient.mul_with_ref(&t);
+            previous_t = std::mem::replace(&mut t, next_t);
+        }
+
+        if previous_r == Polynomial::zero() {
+            return (previous_r, previous_s, previous_t);
+        }
+        let normalization = Polynomial::new(vec![F::one() / previous_r.leading_coefficient()]);
+        (
+            previous_r.mul_with_ref(&normalization),
+            previous_s.mul_with_ref(&normalization),
+            previous_t.mul_with_ref(&normalization),
+        )
+    }
+
+    /// Computes the inverse of `self` modulo `modulus`, that is, the polynomial `s`
+    /// of degree smaller than `modulus` such that `s * self = 1 mod modulus`.
+    /// Returns `None` if they are not coprime.
+    pub fn inverse_modulo(&self, modulus: &Self) -> Option<Self> {
+        let (gcd, s, _) = self.extended_gcd(modulus);
+        if gcd == Polynomial::new_monomial(F::one(), 0) {
+            let (_quotient, remainder) = s.long_division_with_remainder(modulus);
+            Some(remainder)
+        } else {
+            None
+        }
+    }
+
     pub fn mul_with_ref(&self, factor: &Self) -> Self {
         let degree = self.degree() + factor.degree();
-        let mut coefficients = vec![FieldElement::new(0); degree + 1];
+        let mut coefficients = vec![F::zero(); degree + 1];
 
         if self.coefficients.is_empty() || factor.coefficients.is_empty() {
-            Polynomial::new(vec![FieldElement::new(0)])
+            Polynomial::new(vec![F::zero()])
         } else {
             for i in 0..=factor.degree() {
                 for j in 0..=self.degree() {
-                    coefficients[i + j] += factor.coefficients[i] * self.coefficients[j];
+                    coefficients[i + j] = coefficients[i + j].clone()
+                        + factor.coefficients[i].clone() * self.coefficients[j].clone();
                 }
             }
             Polynomial::new(coefficients)
@@ -148,69 +187,71 @@ impl<const ORDER: u128> Polynomial<ORDER> {
     }
 }
 
-impl<const ORDER: u128> ops::Add<&Polynomial<ORDER>> for &Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Add<&Polynomial<F>> for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn add(self, a_polynomial: &Polynomial<ORDER>) -> Self::Output {
+    fn add(self, a_polynomial: &Polynomial<F>) -> Self::Output {
         let (pa, pb) = Polynomial::pad_with_zero_coefficients(self, a_polynomial);
         let iter_coeff_pa = pa.coefficients.iter();
         let iter_coeff_pb = pb.coefficients.iter();
-        let new_coefficients = iter_coeff_pa.zip(iter_coeff_pb).map(|(&x, &y)| x + y);
+        let new_coefficients = iter_coeff_pa
+            .zip(iter_coeff_pb)
+            .map(|(x, y)| x.clone() + y.clone());
 
         Polynomial::new(new_coefficients.collect())
     }
 }
 
-impl<const ORDER: u128> ops::Add<Polynomial<ORDER>> for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Add<Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn add(self, a_polynomial: Polynomial<ORDER>) -> Polynomial<ORDER> {
+    fn add(self, a_polynomial: Polynomial<F>) -> Polynomial<F> {
         &self + &a_polynomial
     }
 }
 
-impl<const ORDER: u128> ops::Add<&Polynomial<ORDER>> for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Add<&Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn add(self, a_polynomial: &Polynomial<ORDER>) -> Polynomial<ORDER> {
+    fn add(self, a_polynomial: &Polynomial<F>) -> Polynomial<F> {
         &self + a_polynomial
     }
 }
 
-impl<const ORDER: u128> ops::Add<Polynomial<ORDER>> for &Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Add<Polynomial<F>> for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn add(self, a_polynomial: Polynomial<ORDER>) -> Polynomial<ORDER> {
+    fn add(self, a_polynomial: Polynomial<F>) -> Polynomial<F> {
         self + &a_polynomial
     }
 }
-impl<const ORDER: u128> ops::Neg for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Neg for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn neg(self) -> Polynomial<ORDER> {
-        Polynomial::new(self.coefficients.iter().map(|&x| -x).collect())
+    fn neg(self) -> Polynomial<F> {
+        Polynomial::new(self.coefficients.into_iter().map(|x| -x).collect())
     }
 }
 
-impl<const ORDER: u128> ops::Sub<Polynomial<ORDER>> for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Sub<Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn sub(self, substrahend: Polynomial<ORDER>) -> Polynomial<ORDER> {
+    fn sub(self, substrahend: Polynomial<F>) -> Polynomial<F> {
         self + (-substrahend)
     }
 }
 
-impl<const ORDER: u128> ops::Div<Polynomial<ORDER>> for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
+impl<F: Field> ops::Div<Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-    fn div(self, dividend: Polynomial<ORDER>) -> Polynomial<ORDER> {
+    fn div(self, dividend: Polynomial<F>) -> Polynomial<F> {
         self.div_with_ref(&dividend)
     }
 }
 
-impl<const ORDER: u128> ops::Mul<Polynomial<ORDER>> for Polynomial<ORDER> {
-    type Output = Polynomial<ORDER>;
-    fn mul(self, dividend: Polynomial<ORDER>) -> Polynomial<ORDER> {
+impl<F: Field> ops::Mul<Polynomial<F>> for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn mul(self, dividend: Polynomial<F>) -> Polynomial<F> {
         self.mul_with_ref(&dividend)
     }
 }
@@ -219,14 +260,15 @@ impl<const ORDER: u128> ops::Mul<Polynomial<ORDER>> for Polynomial<ORDER> {
 mod tests {
     // Some of these tests work when the finite field has order greater than 2.
     use super::*;
+    use crate::math::field_element::FieldElement;
     const ORDER: u128 = 23;
     type FE = FieldElement<ORDER>;
 
-    fn polynomial_a() -> Polynomial<ORDER> {
+    fn polynomial_a() -> Polynomial<FE> {
         Polynomial::new(vec![FE::new(1), FE::new(2), FE::new(3)])
     }
 
-    fn polynomial_minus_a() -> Polynomial<ORDER> {
+    fn polynomial_minus_a() -> Polynomial<FE> {
         Polynomial::new(vec![
             FE::new(ORDER - 1),
             FE::new(ORDER - 2),
@@ -234,15 +276,15 @@ mod tests {
         ])
     }
 
-    fn polynomial_b() -> Polynomial<ORDER> {
+    fn polynomial_b() -> Polynomial<FE> {
         Polynomial::new(vec![FE::new(3), FE::new(4), FE::new(5)])
     }
 
-    fn polynomial_a_plus_b() -> Polynomial<ORDER> {
+    fn polynomial_a_plus_b() -> Polynomial<FE> {
         Polynomial::new(vec![FE::new(4), FE::new(6), FE::new(8)])
     }
 
-    fn polynomial_b_minus_a() -> Polynomial<ORDER> {
+    fn polynomial_b_minus_a() -> Polynomial<FE> {
         Polynomial::new(vec![FE::new(2), FE::new(2), FE::new(2)])
     }
 
@@ -470,4 +512,37 @@ mod tests {
         let p = Polynomial::interpolate(&[FE::new(0)], &[FE::new(0)]);
         assert_eq!(FE::new(0), p.evaluate(FE::new(0)));
     }
+
+    #[test]
+    fn extended_gcd_of_polynomials_with_a_common_root() {
+        // (X - 1) * (X - 2) and (X - 1) * (X + 5)
+        let a = Polynomial::new(vec![FE::new(2), -FE::new(3), FE::new(1)]);
+        let b = Polynomial::new(vec![-FE::new(5), FE::new(4), FE::new(1)]);
+        let (g, s, t) = a.extended_gcd(&b);
+        assert_eq!(g, Polynomial::new(vec![-FE::new(1), FE::new(1)]));
+        assert_eq!(s.mul_with_ref(&a) + t.mul_with_ref(&b), g);
+        assert_eq!(a.inverse_modulo(&b), None);
+    }
+
+    #[test]
+    fn inverse_modulo_an_irreducible_polynomial() {
+        // X^3 + X + 3 has no roots in F_23, so it is irreducible.
+        let modulus = Polynomial::new(vec![FE::new(3), FE::new(1), FE::new(0), FE::new(1)]);
+        let one = Polynomial::new(vec![FE::new(1)]);
+        for coefficients in [[1, 0, 0], [0, 1, 0], [5, 0, 7], [1, 2, 3], [22, 13, 4]] {
+            let p = Polynomial::new(coefficients.map(FE::new).to_vec());
+            let inverse = p.inverse_modulo(&modulus).unwrap();
+            assert!(inverse.degree() < modulus.degree());
+            let (_quotient, remainder) = inverse
+                .mul_with_ref(&p)
+                .long_division_with_remainder(&modulus);
+            assert_eq!(remainder, one);
+        }
+    }
+
+    #[test]
+    fn zero_has_no_inverse_modulo_a_polynomial() {
+        let modulus = Polynomial::new(vec![FE::new(1), FE::new(0), FE::new(1)]);
+        assert_eq!(Polynomial::zero().inverse_modulo(&modulus), None);
+    }
 }
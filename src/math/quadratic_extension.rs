@@ -0,0 +1,422 @@
+use super::cubic_extension::{CubicExtensionElement, CubicExtensionParams};
+use super::cyclic_group::Group;
+use super::field::{ConditionalSwap, Field, FrobeniusField, SqrtField};
+use super::field_element::FieldElementError;
+use super::unsigned_integer::IsUnsignedInteger;
+use std::fmt::Debug;
+use std::ops;
+
+/// Parameters of the quadratic extension F[u] / (u^2 - non_residue)
+/// of a base field F.
+pub trait QuadraticExtensionParams: Debug + Clone + PartialEq + Eq {
+    type BaseField: Field;
+
+    /// Returns the quadratic non-residue whose square root `u` is adjoined
+    /// to the base field.
+    fn non_residue() -> Self::BaseField;
+
+    /// Returns `u^(p - 1) = non_residue^((p - 1) / 2)`, where p is the characteristic,
+    /// so that the Frobenius map sends `u` to `frobenius_coefficient() * u`.
+    fn frobenius_coefficient() -> Self::BaseField;
+}
+
+/// Represents the element `c0 + c1 * u` of a quadratic extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuadraticExtensionElement<P: QuadraticExtensionParams> {
+    c0: P::BaseField,
+    c1: P::BaseField,
+}
+
+impl<P: QuadraticExtensionParams> QuadraticExtensionElement<P> {
+    /// Creates the element `c0 + c1 * u`.
+    pub fn new(c0: P::BaseField, c1: P::BaseField) -> Self {
+        Self { c0, c1 }
+    }
+
+    /// Creates an element belonging to the base field.
+    pub fn new_base(c0: P::BaseField) -> Self {
+        Self::new(c0, P::BaseField::zero())
+    }
+
+    /// Returns the coefficient of `u^0`.
+    pub fn c0(&self) -> &P::BaseField {
+        &self.c0
+    }
+
+    /// Returns the coefficient of `u^1`.
+    pub fn c1(&self) -> &P::BaseField {
+        &self.c1
+    }
+
+    /// Returns `c0 - c1 * u`.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0.clone(), -self.c1.clone())
+    }
+
+    /// Multiplies every coefficient by an element of the base field.
+    pub fn mul_by_base(&self, factor: &P::BaseField) -> Self {
+        Self::new(
+            self.c0.clone() * factor.clone(),
+            self.c1.clone() * factor.clone(),
+        )
+    }
+}
+
+impl<P: QuadraticExtensionParams> ops::Add for QuadraticExtensionElement<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.c0 + other.c0, self.c1 + other.c1)
+    }
+}
+
+impl<P: QuadraticExtensionParams> ops::Neg for QuadraticExtensionElement<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.c0, -self.c1)
+    }
+}
+
+impl<P: QuadraticExtensionParams> ops::Sub for QuadraticExtensionElement<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.c0 - other.c0, self.c1 - other.c1)
+    }
+}
+
+impl<P: QuadraticExtensionParams> ops::Mul for QuadraticExtensionElement<P> {
+    type Output = Self;
+
+    /// Karatsuba multiplication: three multiplications in the base field
+    /// plus one by the non-residue.
+    fn mul(self, other: Self) -> Self {
+        let v0 = self.c0.clone() * other.c0.clone();
+        let v1 = self.c1.clone() * other.c1.clone();
+        let c1 = (self.c0 + self.c1) * (other.c0 + other.c1) - v0.clone() - v1.clone();
+        Self::new(v0 + P::non_residue() * v1, c1)
+    }
+}
+
+impl<P: QuadraticExtensionParams> ops::Div for QuadraticExtensionElement<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, dividend: Self) -> Self {
+        self * Field::inv(&dividend).unwrap()
+    }
+}
+
+impl<P: QuadraticExtensionParams> Field for QuadraticExtensionElement<P> {
+    const EXTENSION_DEGREE: usize = 2 * P::BaseField::EXTENSION_DEGREE;
+
+    fn zero() -> Self {
+        Self::new_base(P::BaseField::zero())
+    }
+
+    fn one() -> Self {
+        Self::new_base(P::BaseField::one())
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::new_base(P::BaseField::from_u64(value))
+    }
+
+    /// Computes the inverse as `conjugate / norm`, where the norm
+    /// `c0^2 - non_residue * c1^2` lives in the base field.
+    fn inv(&self) -> Result<Self, FieldElementError> {
+        let norm = self.c0.clone() * self.c0.clone()
+            - P::non_residue() * self.c1.clone() * self.c1.clone();
+        let norm_inverse = norm.inv()?;
+        Ok(self.conjugate().mul_by_base(&norm_inverse))
+    }
+
+    fn random() -> Self {
+        Self::new(P::BaseField::random(), P::BaseField::random())
+    }
+
+    /// Concatenates the encodings of the coefficients, highest degree first.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.c1.to_bytes();
+        bytes.extend(self.c0.to_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FieldElementError> {
+        let length = P::BaseField::byte_length();
+        if bytes.len() != 2 * length {
+            return Err(FieldElementError::InvalidLength);
+        }
+        let c1 = P::BaseField::from_bytes(&bytes[..length])?;
+        let c0 = P::BaseField::from_bytes(&bytes[length..])?;
+        Ok(Self::new(c0, c1))
+    }
+}
+
+impl<P: QuadraticExtensionParams> ConditionalSwap for QuadraticExtensionElement<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        P::BaseField::conditional_swap(&mut a.c0, &mut b.c0, mask);
+        P::BaseField::conditional_swap(&mut a.c1, &mut b.c1, mask);
+    }
+}
+
+impl<P: QuadraticExtensionParams> FrobeniusField for QuadraticExtensionElement<P>
+where
+    P::BaseField: FrobeniusField,
+{
+    /// (c0 + c1 * u)^p = c0^p + c1^p * u^(p - 1) * u
+    fn frobenius(&self) -> Self {
+        Self::new(
+            self.c0.frobenius(),
+            self.c1.frobenius() * P::frobenius_coefficient(),
+        )
+    }
+}
+
+/// The sign compares `c1` first, and the encoding starts with it,
+/// so its unused bits are those of the base field.
+impl<P: QuadraticExtensionParams> SqrtField for QuadraticExtensionElement<P>
+where
+    P::BaseField: SqrtField,
+{
+    /// If (x0 + x1 * u)^2 = c0 + c1 * u, then x0^2 = (c0 +- sqrt(norm)) / 2, with
+    /// norm = c0^2 - non_residue * c1^2, and x1 = c1 / (2 * x0), so it only needs
+    /// square roots in the base field.
+    /// See "Square root computation over even extension fields", Algorithm 8.
+    fn sqrt(&self) -> Option<Self> {
+        if self.c1 == P::BaseField::zero() {
+            // Either c0 or c0 / non_residue is a square in the base field.
+            return match self.c0.sqrt() {
+                Some(root) => Some(Self::new_base(root)),
+                None => (self.c0.clone() / P::non_residue())
+                    .sqrt()
+                    .map(|root| Self::new(P::BaseField::zero(), root)),
+            };
+        }
+        let norm = self.c0.clone() * self.c0.clone()
+            - P::non_residue() * self.c1.clone() * self.c1.clone();
+        let norm_root = norm.sqrt()?;
+        let two = P::BaseField::from_u64(2);
+        let x0 = ((self.c0.clone() + norm_root.clone()) / two.clone())
+            .sqrt()
+            .or_else(|| ((self.c0.clone() - norm_root) / two.clone()).sqrt())
+            .expect("One of the two candidates is a square.");
+        // x0 is not zero, since otherwise the square would have c1 = 0.
+        let x1 = self.c1.clone() / (two * x0.clone());
+        Some(Self::new(x0, x1))
+    }
+
+    fn is_lexicographically_largest(&self) -> bool {
+        if self.c1 == P::BaseField::zero() {
+            self.c0.is_lexicographically_largest()
+        } else {
+            self.c1.is_lexicographically_largest()
+        }
+    }
+
+    fn unused_leading_bits() -> usize {
+        P::BaseField::unused_leading_bits()
+    }
+}
+
+/// Elements of a degree 12 tower F2[v, w] / (v^3 - xi, w^2 - v), built as a quadratic
+/// extension of a cubic extension of a quadratic field F2.
+impl<P, Q> QuadraticExtensionElement<P>
+where
+    P: QuadraticExtensionParams<BaseField = CubicExtensionElement<Q>>,
+    Q: CubicExtensionParams,
+{
+    /// Squares an element of the cyclotomic subgroup, the elements whose
+    /// norm down to the subfield of index 2 is one. These are the values
+    /// of the pairing after the easy part of the final exponentiation.
+    /// Only valid when the non-residue of the quadratic extension is `v`.
+    /// See "Faster Squaring in the Cyclotomic Subgroup of Sixth Degree
+    /// Extensions", section 3.2.
+    pub fn cyclotomic_square(&self) -> Self {
+        let xi = Q::non_residue;
+        let two = |x: Q::BaseField| x.clone() + x;
+        let three = |x: Q::BaseField| x.clone() + x.clone() + x;
+        let (z0, z4, z3) = (
+            self.c0.c0().clone(),
+            self.c0.c1().clone(),
+            self.c0.c2().clone(),
+        );
+        let (z2, z1, z5) = (
+            self.c1.c0().clone(),
+            self.c1.c1().clone(),
+            self.c1.c2().clone(),
+        );
+
+        // (a + b * s)^2 = (a^2 + xi * b^2) + 2 * a * b * s, where s^2 = xi
+        let square = |a: &Q::BaseField, b: &Q::BaseField| {
+            let ab = a.clone() * b.clone();
+            let real = (a.clone() + b.clone()) * (a.clone() + xi() * b.clone())
+                - ab.clone()
+                - xi() * ab.clone();
+            (real, two(ab))
+        };
+        let (t0, t1) = square(&z0, &z1);
+        let (t2, t3) = square(&z2, &z3);
+        let (t4, t5) = square(&z4, &z5);
+
+        let z0 = three(t0) - two(z0);
+        let z1 = three(t1) + two(z1);
+        let z2 = three(xi() * t5) + two(z2);
+        let z3 = three(t4) - two(z3);
+        let z4 = three(t2) - two(z4);
+        let z5 = three(t3) + two(z5);
+        Self::new(
+            CubicExtensionElement::new(z0, z4, z3),
+            CubicExtensionElement::new(z2, z1, z5),
+        )
+    }
+
+    /// Returns `self` to the power of `exponent`, for `self` in the cyclotomic subgroup,
+    /// using `cyclotomic_square` in the left-to-right binary method.
+    pub fn cyclotomic_pow<T: IsUnsignedInteger>(&self, exponent: T) -> Self {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let mut bits = vec![];
+        let mut exponent = exponent;
+        while exponent > zero {
+            bits.push(exponent & one == one);
+            exponent = exponent >> 1;
+        }
+        bits.iter().rev().fold(Self::one(), |result, bit| {
+            let result = result.cyclotomic_square();
+            if *bit {
+                result * self.clone()
+            } else {
+                result
+            }
+        })
+    }
+}
+
+/// The multiplicative group of the extension, where pairings take their values.
+impl<P: QuadraticExtensionParams> Group for QuadraticExtensionElement<P> {
+    fn neutral_element() -> Self {
+        Self::one()
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        self.clone() * other.clone()
+    }
+
+    fn inverse(&self) -> Self {
+        Field::inv(self).expect("Zero is not in the multiplicative group.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<59>;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct MinusOne;
+    impl QuadraticExtensionParams for MinusOne {
+        type BaseField = FE;
+
+        fn non_residue() -> FE {
+            -FE::new(1)
+        }
+
+        // (-1)^((59 - 1) / 2)
+        fn frobenius_coefficient() -> FE {
+            -FE::new(1)
+        }
+    }
+    #[allow(clippy::upper_case_acronyms)]
+    type FEE = QuadraticExtensionElement<MinusOne>;
+
+    #[test]
+    fn mul_matches_polynomial_multiplication_modulo_x_squared_plus_one() {
+        let a = FEE::new(FE::new(12), FE::new(5));
+        let b = FEE::new(-FE::new(4), FE::new(2));
+        assert_eq!(a * b, FEE::new(FE::new(1), FE::new(4)));
+    }
+
+    #[test]
+    fn div_matches_polynomial_division_modulo_x_squared_plus_one() {
+        let a = FEE::new(FE::new(12), FE::new(5));
+        let b = FEE::new(-FE::new(4), FE::new(2));
+        assert_eq!(a / b, FEE::new(FE::new(4), FE::new(45)));
+    }
+
+    #[test]
+    fn u_squared_is_the_non_residue() {
+        let u = FEE::new(FE::new(0), FE::new(1));
+        assert_eq!(u.clone() * u, FEE::new_base(MinusOne::non_residue()));
+    }
+
+    #[test]
+    fn inverse_of_zero_is_an_error() {
+        assert_eq!(FEE::zero().inv(), Err(FieldElementError::DivisionByZero));
+    }
+
+    #[test]
+    fn from_bytes_inverts_to_bytes() {
+        let a = FEE::new(FE::new(1), FE::new(2));
+        assert_eq!(FEE::from_bytes(&a.to_bytes()), Ok(a));
+        assert_eq!(
+            FEE::from_bytes(&[59, 0]),
+            Err(FieldElementError::OutOfRangeValue)
+        );
+        assert_eq!(FEE::from_bytes(&[1]), Err(FieldElementError::InvalidLength));
+    }
+
+    #[test]
+    fn to_bytes_puts_the_highest_coefficient_first() {
+        let a = FEE::new(FE::new(1), FE::new(2));
+        assert_eq!(a.to_bytes(), vec![2, 1]);
+    }
+
+    #[test]
+    fn frobenius_is_the_p_power() {
+        for c0 in [0, 1, 7, 58] {
+            for c1 in [0, 1, 30, 41] {
+                let a = FEE::new(FE::new(c0), FE::new(c1));
+                assert_eq!(a.frobenius(), a.pow(59u64));
+                assert_eq!(a.frobenius_power(2), a);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_finds_the_roots_of_every_square() {
+        let mut squares = 0;
+        for c0 in 0..59 {
+            for c1 in 0..59 {
+                let a = FEE::new(FE::new(c0), FE::new(c1));
+                if let Some(root) = a.sqrt() {
+                    assert_eq!(root.clone() * root, a);
+                    squares += 1;
+                }
+            }
+        }
+        // Zero and half of the non-zero elements.
+        assert_eq!(squares, (59 * 59 + 1) / 2);
+    }
+
+    #[test]
+    fn sign_and_unused_bits_follow_the_highest_coefficient() {
+        let a = FEE::new(FE::new(1), FE::new(2));
+        assert!(!a.is_lexicographically_largest());
+        assert!((-a).is_lexicographically_largest());
+        assert!(FEE::new(FE::new(58), FE::new(0)).is_lexicographically_largest());
+        // 58 takes 6 of the 8 bits of its byte.
+        assert_eq!(FEE::unused_leading_bits(), 2);
+    }
+
+    #[test]
+    fn extension_degree_counts_the_prime_field_as_one() {
+        assert_eq!(FEE::EXTENSION_DEGREE, 2);
+    }
+}
@@ -0,0 +1,170 @@
+use super::cyclic_group::Group;
+use super::field::{swap_mask, ConditionalSwap, PrimeField};
+use super::unsigned_integer::{bit_length, IsUnsignedInteger};
+
+/// Computes `times * point` from the width-w NAF of `times`, going from the
+/// most significant digit down. Negative digits subtract a precomputed odd
+/// multiple, which is as cheap as adding it when inversion is free, as it is
+/// for curve points.
+/// `double` has to compute `2 * point` and `batch_normalize` can bring the odd
+/// multiples to a form that is cheaper to add, like affine points.
+pub fn wnaf_operate_with_self<G: Group, T: IsUnsignedInteger>(
+    point: &G,
+    times: T,
+    double: impl Fn(&G) -> G,
+    batch_normalize: impl Fn(&[G]) -> Vec<G>,
+) -> G {
+    let w = wnaf_width(bit_length(times));
+    let digits = wnaf(times, w);
+
+    // odd_multiples[i] = (2 * i + 1) * point
+    let point_doubled = double(point);
+    let mut odd_multiples = vec![point.clone()];
+    for i in 1..(1 << (w - 2)) {
+        odd_multiples.push(odd_multiples[i - 1].operate_with(&point_doubled));
+    }
+    let odd_multiples = batch_normalize(&odd_multiples);
+
+    let mut result = G::neutral_element();
+    for &digit in digits.iter().rev() {
+        result = double(&result);
+        if digit > 0 {
+            result = result.operate_with(&odd_multiples[(digit as usize - 1) / 2]);
+        } else if digit < 0 {
+            result = result.operate_with(&odd_multiples[(-digit as usize - 1) / 2].inverse());
+        }
+    }
+    result
+}
+
+/// Computes `scalar * point` with the Montgomery ladder.
+/// Every bit of the order of the scalar field is processed with exactly one
+/// addition and one doubling, whatever its value, so the sequence of operations
+/// does not depend on the scalar, and the two accumulators are exchanged with
+/// `ConditionalSwap` instead of branching on the bits. Use it when the scalar
+/// is secret, for example when hiding the toxic waste.
+/// Note that the group arithmetic itself is not constant time.
+pub fn montgomery_ladder<G: Group + ConditionalSwap, F: PrimeField>(
+    point: &G,
+    scalar: &F,
+    double: impl Fn(&G) -> G,
+) -> G {
+    let k = scalar.representative();
+    let one = F::RepresentativeType::from(1);
+    // Invariant: r1 = r0 + point
+    let mut r0 = G::neutral_element();
+    let mut r1 = point.clone();
+    for i in (0..F::representative_bits()).rev() {
+        let mask = swap_mask((k >> i) & one == one);
+        G::conditional_swap(&mut r0, &mut r1, mask);
+        r1 = r0.operate_with(&r1);
+        r0 = double(&r0);
+        G::conditional_swap(&mut r0, &mut r1, mask);
+    }
+    r0
+}
+
+/// Returns the width to use in the wNAF of a scalar of `bits` bits.
+/// Wider windows need fewer additions but a bigger table of precomputed points.
+fn wnaf_width(bits: usize) -> usize {
+    match bits {
+        0..=16 => 2,
+        17..=64 => 3,
+        65..=192 => 4,
+        _ => 5,
+    }
+}
+
+/// Computes the width-`w` non-adjacent form of `k`: signed digits d_i, least
+/// significant first, such that k = sum_i d_i * 2^i, every non-zero digit is odd
+/// and smaller than 2^(w - 1) in absolute value, and among any `w` consecutive
+/// digits at most one is non-zero.
+/// See "Guide to Elliptic Curve Cryptography", Algorithm 3.35.
+pub fn wnaf<T: IsUnsignedInteger>(mut k: T, w: usize) -> Vec<i64> {
+    let zero = T::from(0);
+    let one = T::from(1);
+    let mut bits = vec![];
+    while k > zero {
+        bits.push(k & one == one);
+        k = k >> 1;
+    }
+
+    let width = 1i64 << w;
+    let bit = |i: usize| *bits.get(i).unwrap_or(&false) as i64;
+    // One extra digit for the carry out of the most significant bit.
+    let mut digits = vec![0; bits.len() + 1];
+    let mut carry = 0;
+    let mut position = 0;
+    while position < digits.len() {
+        let window = carry + (0..w).map(|j| bit(position + j) << j).sum::<i64>();
+        if window & 1 == 0 {
+            // The digit is zero, and when the carry met a set bit it moves up.
+            position += 1;
+            continue;
+        }
+        if window < width / 2 {
+            carry = 0;
+            digits[position] = window;
+        } else {
+            carry = 1;
+            digits[position] = window - width;
+        }
+        position += w;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::cyclic_group::CyclicGroup;
+    use crate::math::field_element::FieldElement;
+
+    // The integers modulo 1009 under addition, where doubling is adding
+    // an element to itself and there is nothing to normalize.
+    type FE = FieldElement<1009>;
+
+    fn double(a: &FE) -> FE {
+        a.operate_with(a)
+    }
+
+    #[test]
+    fn wnaf_digits_add_up_to_the_scalar_and_are_non_adjacent() {
+        for w in 2..=5 {
+            for k in [0u64, 1, 7, 255, 1000, 0xdeadbeef, u64::MAX] {
+                let digits = wnaf(k, w);
+                let value: i128 = digits
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &d)| (d as i128) << i)
+                    .sum();
+                assert_eq!(value, k as i128);
+                for (i, &d) in digits.iter().enumerate() {
+                    if d != 0 {
+                        assert_eq!(d & 1, 1);
+                        assert!(d.abs() < 1 << (w - 1));
+                        assert!(digits[i + 1..].iter().take(w - 1).all(|&e| e == 0));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wnaf_operate_with_self_matches_the_product() {
+        let g = FE::new(3);
+        for k in [0u64, 1, 2, 5, 1000, 123456789, u64::MAX] {
+            let result = wnaf_operate_with_self(&g, k, double, <[FE]>::to_vec);
+            assert_eq!(result, g * FE::new((k % 1009) as u128));
+        }
+    }
+
+    #[test]
+    fn montgomery_ladder_matches_the_product() {
+        let g = FE::new(3);
+        for k in [0, 1, 2, 500, 1008] {
+            let k = FE::new(k);
+            assert_eq!(montgomery_ladder(&g, &k, double), g.operate_with_scalar(&k));
+        }
+    }
+}
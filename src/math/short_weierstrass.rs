@@ -0,0 +1,805 @@
+use super::cyclic_group::{CyclicGroup, Group};
+use super::elliptic_curve::EllipticCurveError;
+use super::field::{ConditionalSwap, Field, PrimeField, SqrtField};
+use super::scalar_multiplication;
+use super::unsigned_integer::IsUnsignedInteger;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+use std::ops;
+
+/// Parameters of an elliptic curve in short Weierstrass form:
+///   y^2 = x^3 + a * x + b
+/// whose points have coordinates in `BaseField`.
+pub trait ShortWeierstrassParams: Debug + Clone + PartialEq + Eq {
+    type BaseField: Field;
+    /// Field of the scalars, its order is the order of the generator.
+    type ScalarField: PrimeField;
+    /// Type wide enough to hold `COFACTOR`.
+    type Cofactor: IsUnsignedInteger;
+
+    /// Number of points of the curve divided by the order of the generator.
+    const COFACTOR: Self::Cofactor;
+
+    /// Returns the coefficient `a` of the defining equation.
+    fn a() -> Self::BaseField;
+    /// Returns the coefficient `b` of the defining equation.
+    fn b() -> Self::BaseField;
+    /// Returns the affine coordinates (x, y) of the generator.
+    fn generator_affine() -> (Self::BaseField, Self::BaseField);
+
+    /// Maps a point of the curve to the subgroup of the generator, or to the point
+    /// at infinity. Multiplying by `COFACTOR` does it as long as the generator
+    /// spans the only subgroup of its order.
+    fn clear_cofactor(point: &ShortWeierstrassPoint<Self>) -> ShortWeierstrassPoint<Self> {
+        point.operate_with_self(Self::COFACTOR)
+    }
+}
+
+/// Flags of the three most significant bits of an encoded point.
+const COMPRESSED_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+const SIGN_FLAG: u8 = 0x20;
+const FLAGS: u8 = COMPRESSED_FLAG | INFINITY_FLAG | SIGN_FLAG;
+
+/// Represents an elliptic curve point using Jacobian coordinates, where (x, y, z)
+/// stands for the affine point (x / z^2, y / z^3). They satisfy the equation:
+///   y^2 = x^3 + a * x * z^4 + b * z^6
+/// The point at infinity is any point with z = 0.
+/// Curves with an asymmetric pairing use two of these, one for each source group,
+/// and `EllipticCurveElement` is one over the quadratic extension of its base field.
+///
+/// Only the constructors check that the coordinates satisfy the equation.
+/// The group operations build their results directly, since they always land on the curve.
+#[derive(Debug, Clone)]
+pub struct ShortWeierstrassPoint<C: ShortWeierstrassParams> {
+    x: C::BaseField,
+    y: C::BaseField,
+    z: C::BaseField,
+}
+
+impl<C: ShortWeierstrassParams> ShortWeierstrassPoint<C> {
+    /// Creates an elliptic curve point giving its Jacobian (x, y, z) coordinates.
+    /// Panics if the point does not belong to the elliptic curve.
+    pub fn new(x: C::BaseField, y: C::BaseField, z: C::BaseField) -> Self {
+        Self::try_new(x, y, z).expect("The point does not belong to the elliptic curve.")
+    }
+
+    /// Creates an elliptic curve point giving its Jacobian (x, y, z) coordinates.
+    /// Fails if they do not satisfy the equation of the curve.
+    pub fn try_new(
+        x: C::BaseField,
+        y: C::BaseField,
+        z: C::BaseField,
+    ) -> Result<Self, EllipticCurveError> {
+        if Self::defining_equation(&x, &y, &z) == C::BaseField::zero() {
+            Ok(Self { x, y, z })
+        } else {
+            Err(EllipticCurveError::InvalidPoint)
+        }
+    }
+
+    /// Creates an elliptic curve point from coordinates already known to satisfy
+    /// the equation of the curve, like the image of a point under an endomorphism.
+    pub(crate) fn new_unchecked(x: C::BaseField, y: C::BaseField, z: C::BaseField) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Creates an elliptic curve point giving its affine (x, y) coordinates.
+    /// Fails if they do not satisfy the equation of the curve.
+    pub fn from_affine(x: C::BaseField, y: C::BaseField) -> Result<Self, EllipticCurveError> {
+        Self::try_new(x, y, C::BaseField::one())
+    }
+
+    /// Evaluates the short Weierstrass equation in Jacobian coordinates at (x, y z).
+    /// Useful for checking if (x, y, z) belongs to the elliptic curve.
+    fn defining_equation(x: &C::BaseField, y: &C::BaseField, z: &C::BaseField) -> C::BaseField {
+        let z2 = z.pow(2u64);
+        y.pow(2u64) - x.pow(3u64) - C::a() * x.clone() * z2.pow(2u64) - C::b() * z2.pow(3u64)
+    }
+
+    /// Returns `true` if `self` is the point at infinity.
+    pub fn is_neutral_element(&self) -> bool {
+        self.z == C::BaseField::zero()
+    }
+
+    pub fn x(&self) -> &C::BaseField {
+        &self.x
+    }
+
+    pub fn y(&self) -> &C::BaseField {
+        &self.y
+    }
+
+    pub fn z(&self) -> &C::BaseField {
+        &self.z
+    }
+
+    /// Returns the affine coordinates (x / z^2, y / z^3).
+    /// Panics if `self` is the point at infinity.
+    pub fn to_affine(&self) -> (C::BaseField, C::BaseField) {
+        let point = self.normalize();
+        (point.x, point.y)
+    }
+
+    /// Normalize the Jacobian coordinates to obtain affine coordinates
+    /// of the form (x, y, 1)
+    /// Panics if `self` is the point at infinity
+    pub fn normalize(&self) -> Self {
+        assert!(
+            !self.is_neutral_element(),
+            "The point at infinity is not affine."
+        );
+        self.scale_by_z_inverse(&self.z.inv().unwrap())
+    }
+
+    /// Returns (x * z_inverse^2, y * z_inverse^3, 1), which is the affine form
+    /// of `self` when `z_inverse` is the inverse of its z coordinate.
+    fn scale_by_z_inverse(&self, z_inverse: &C::BaseField) -> Self {
+        let z_inverse_2 = z_inverse.pow(2u64);
+        Self {
+            x: self.x.clone() * z_inverse_2.clone(),
+            y: self.y.clone() * z_inverse_2 * z_inverse.clone(),
+            z: C::BaseField::one(),
+        }
+    }
+
+    /// Normalizes all the points to affine coordinates with a single inversion,
+    /// using Montgomery's trick. Points at infinity are left as they are.
+    /// Adding an affine point is cheaper, see `operate_with`.
+    pub fn batch_normalize(points: &[Self]) -> Vec<Self> {
+        // prefix_products[i] is the product of the z coordinates of points[..i]
+        let mut prefix_products = Vec::with_capacity(points.len());
+        let mut product = C::BaseField::one();
+        for point in points {
+            prefix_products.push(product.clone());
+            if !point.is_neutral_element() {
+                product = product * point.z.clone();
+            }
+        }
+
+        // Walking backwards, `inverse` is the inverse of the product of the z coordinates of points[..=i]
+        let mut inverse = product
+            .inv()
+            .expect("Only non-zero z coordinates are multiplied.");
+        let mut normalized: Vec<Self> = points
+            .iter()
+            .zip(prefix_products)
+            .rev()
+            .map(|(point, prefix_product)| {
+                if point.is_neutral_element() {
+                    point.clone()
+                } else {
+                    let z_inverse = inverse.clone() * prefix_product;
+                    inverse = inverse.clone() * point.z.clone();
+                    point.scale_by_z_inverse(&z_inverse)
+                }
+            })
+            .collect();
+        normalized.reverse();
+        normalized
+    }
+
+    /// Computes `2 * self`.
+    /// See "dbl-2007-bl" at https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html,
+    /// here without the squaring tricks.
+    fn double(&self) -> Self {
+        if self.is_neutral_element() || self.y == C::BaseField::zero() {
+            return Self::neutral_element();
+        }
+        let xx = self.x.pow(2u64);
+        let yy = self.y.pow(2u64);
+        let yyyy = yy.pow(2u64);
+        let zz = self.z.pow(2u64);
+        let s = C::BaseField::from_u64(4) * self.x.clone() * yy;
+        let m = C::BaseField::from_u64(3) * xx + C::a() * zz.pow(2u64);
+        let x = m.pow(2u64) - C::BaseField::from_u64(2) * s.clone();
+        let y = m * (s - x.clone()) - C::BaseField::from_u64(8) * yyyy;
+        let z = C::BaseField::from_u64(2) * self.y.clone() * self.z.clone();
+        Self { x, y, z }
+    }
+
+    /// Computes `self + other` given U1 = x1 * z2^2, U2 = x2 * z1^2,
+    /// S1 = y1 * z2^3, S2 = y2 * z1^3 and z1 * z2.
+    /// See "add-2007-bl" at https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html.
+    fn add_with_scaled_coordinates(
+        &self,
+        [u1, u2, s1, s2]: [C::BaseField; 4],
+        z1_z2: C::BaseField,
+    ) -> Self {
+        let h = u2 - u1.clone();
+        let r = s2 - s1.clone();
+        if h == C::BaseField::zero() {
+            return if r == C::BaseField::zero() {
+                self.double()
+            } else {
+                Self::neutral_element()
+            };
+        }
+        let hh = h.pow(2u64);
+        let hhh = h.clone() * hh.clone();
+        let v = u1 * hh;
+        let x = r.pow(2u64) - hhh.clone() - C::BaseField::from_u64(2) * v.clone();
+        let y = r * (v - x.clone()) - s1 * hhh;
+        let z = z1_z2 * h;
+        Self { x, y, z }
+    }
+
+    /// Computes `scalar * self` with the Montgomery ladder,
+    /// see `scalar_multiplication::montgomery_ladder`.
+    pub fn montgomery_ladder(&self, scalar: &C::ScalarField) -> Self
+    where
+        C::BaseField: ConditionalSwap,
+    {
+        scalar_multiplication::montgomery_ladder(self, scalar, Self::double)
+    }
+}
+
+/// Points are encoded by their affine coordinates, x alone when compressed, with
+/// three flags in the most significant bits of the first byte: compression,
+/// infinity and the sign of y. The flags take the unused bits of x when the base
+/// field leaves three of them, as in the ZCash format used for BLS12-381, and
+/// a leading byte of their own otherwise.
+impl<C: ShortWeierstrassParams> ShortWeierstrassPoint<C>
+where
+    C::BaseField: SqrtField,
+{
+    /// Encodes `self` as its affine coordinates x and y, and the flags.
+    /// The point at infinity is encoded with the infinity flag and zeroed coordinates.
+    pub fn to_bytes_uncompressed(&self) -> Vec<u8> {
+        self.to_bytes(false)
+    }
+
+    /// Encodes `self` as its affine coordinate x, and the flags.
+    /// The sign flag tells which of the two possible values of y is the right one.
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        self.to_bytes(true)
+    }
+
+    /// Returns `true` if the flags need a byte of their own.
+    fn has_flags_byte() -> bool {
+        C::BaseField::unused_leading_bits() < 3
+    }
+
+    fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        let coordinates = if compressed { 1 } else { 2 };
+        let mut flags = if compressed { COMPRESSED_FLAG } else { 0 };
+        let mut bytes = if self.is_neutral_element() {
+            flags |= INFINITY_FLAG;
+            vec![0; coordinates * C::BaseField::byte_length()]
+        } else {
+            let (x, y) = self.to_affine();
+            let mut bytes = x.to_bytes();
+            if compressed {
+                if y.is_lexicographically_largest() {
+                    flags |= SIGN_FLAG;
+                }
+            } else {
+                bytes.extend(y.to_bytes());
+            }
+            bytes
+        };
+        if Self::has_flags_byte() {
+            bytes.insert(0, 0);
+        }
+        bytes[0] |= flags;
+        bytes
+    }
+
+    /// Decodes a point encoded by `to_bytes_uncompressed` or `to_bytes_compressed`,
+    /// telling them apart by the compression flag.
+    /// Only canonical encodings are accepted: unused flags must be unset, the
+    /// coordinates must be reduced and the point must belong to the curve.
+    /// It does not check that the point is in the subgroup of order r.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EllipticCurveError> {
+        let flags = bytes.first().ok_or(EllipticCurveError::InvalidEncoding)? & FLAGS;
+        let compressed = flags & COMPRESSED_FLAG != 0;
+        let sign = flags & SIGN_FLAG != 0;
+        let mut coordinates = bytes.to_vec();
+        coordinates[0] &= !FLAGS;
+        if Self::has_flags_byte() && coordinates.remove(0) != 0 {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let length = C::BaseField::byte_length();
+        if coordinates.len() != if compressed { length } else { 2 * length } {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+
+        if flags & INFINITY_FLAG != 0 {
+            return if !sign && coordinates.iter().all(|byte| *byte == 0) {
+                Ok(Self::neutral_element())
+            } else {
+                Err(EllipticCurveError::InvalidEncoding)
+            };
+        }
+        let field_element = |bytes| {
+            C::BaseField::from_bytes(bytes).map_err(|_| EllipticCurveError::InvalidEncoding)
+        };
+        let x = field_element(&coordinates[..length])?;
+        if !compressed {
+            if sign {
+                return Err(EllipticCurveError::InvalidEncoding);
+            }
+            return Self::from_affine(x, field_element(&coordinates[length..])?);
+        }
+
+        let point = Self::from_x(x, sign).ok_or(EllipticCurveError::InvalidPoint)?;
+        // When y = 0 both roots coincide and the sign flag has to be unset.
+        if point.y.is_lexicographically_largest() != sign {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        Ok(point)
+    }
+
+    /// Returns the point with the given x coordinate, choosing y by its sign as in
+    /// `to_bytes_compressed`, or `None` if x^3 + a * x + b is not a square.
+    pub(crate) fn from_x(x: C::BaseField, sign: bool) -> Option<Self> {
+        let y_squared = x.pow(3u64) + C::a() * x.clone() + C::b();
+        let mut y = y_squared.sqrt()?;
+        if y.is_lexicographically_largest() != sign {
+            y = -y;
+        }
+        Some(Self::new_unchecked(x, y, C::BaseField::one()))
+    }
+}
+
+/// Prefix of the tag hashed in place of a domain separation tag that is too long,
+/// as in section 5.3.3 of the IETF hash-to-curve draft.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Sampling and hashing to the subgroup of the generator.
+impl<C: ShortWeierstrassParams> ShortWeierstrassPoint<C>
+where
+    C::BaseField: SqrtField,
+{
+    /// Maps `self` to the subgroup of the generator, see `ShortWeierstrassParams::clear_cofactor`.
+    pub fn clear_cofactor(&self) -> Self {
+        C::clear_cofactor(self)
+    }
+
+    /// Samples a random point of the subgroup of the generator other than the
+    /// point at infinity. Nobody knows its discrete logarithm with respect to the generator.
+    pub fn random() -> Self {
+        loop {
+            if let Some(point) = Self::from_x(C::BaseField::random(), rand::random()) {
+                let point = point.clear_cofactor();
+                if !point.is_neutral_element() {
+                    return point;
+                }
+            }
+        }
+    }
+
+    /// Deterministically maps `msg` to a point of the subgroup of the generator other
+    /// than the point at infinity, using try-and-increment. The domain separation tag
+    /// `dst` keeps the points of different protocols apart. Nobody knows the discrete
+    /// logarithm of the result, so it can be used as a nothing-up-my-sleeve generator.
+    ///
+    /// For each counter, x is the SHA-256 digest of `hash_to_curve_preimage` reduced
+    /// into the base field and its last bit gives the sign of y. The first x on the
+    /// curve whose point is not killed by clearing the cofactor is the result.
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self {
+        let base = C::BaseField::from_u64(256);
+        for counter in 0_u32.. {
+            let digest = Sha256::digest(hash_to_curve_preimage(msg, dst, counter));
+            let x = digest.iter().fold(C::BaseField::zero(), |x, byte| {
+                x * base.clone() + C::BaseField::from_u64(*byte as u64)
+            });
+            let sign = digest[digest.len() - 1] & 1 == 1;
+            if let Some(point) = Self::from_x(x, sign) {
+                let point = point.clear_cofactor();
+                if !point.is_neutral_element() {
+                    return point;
+                }
+            }
+        }
+        unreachable!("No counter maps the message to the curve.")
+    }
+}
+
+/// Returns the bytes hashed by `hash_to_curve` for `counter`:
+///   len(dst) || dst || counter || msg
+/// where the length takes one byte, so the tag cannot run into the message, and
+/// the counter four. Tags longer than 255 bytes are replaced by
+/// SHA-256("H2C-OVERSIZE-DST-" || dst), as the IETF hash-to-curve draft does.
+fn hash_to_curve_preimage(msg: &[u8], dst: &[u8], counter: u32) -> Vec<u8> {
+    let dst = if dst.len() > u8::MAX as usize {
+        Sha256::new()
+            .chain_update(OVERSIZE_DST_PREFIX)
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let mut preimage = vec![dst.len() as u8];
+    preimage.extend_from_slice(&dst);
+    preimage.extend_from_slice(&counter.to_be_bytes());
+    preimage.extend_from_slice(msg);
+    preimage
+}
+
+impl<C: ShortWeierstrassParams> Group for ShortWeierstrassPoint<C> {
+    /// Returns the point at infinity.
+    fn neutral_element() -> Self {
+        Self {
+            x: C::BaseField::one(),
+            y: C::BaseField::one(),
+            z: C::BaseField::zero(),
+        }
+    }
+
+    /// Computes `times * self` with the width-w NAF of `times`, adding
+    /// odd multiples of `self` normalized to affine so that every addition is mixed.
+    fn operate_with_self<T: IsUnsignedInteger>(&self, times: T) -> Self {
+        scalar_multiplication::wnaf_operate_with_self(
+            self,
+            times,
+            Self::double,
+            Self::batch_normalize,
+        )
+    }
+
+    /// Computes the addition of `self` and `other`.
+    /// When `other` is affine, that is z2 = 1, the mixed addition
+    /// saves the multiplications by z2.
+    fn operate_with(&self, other: &Self) -> Self {
+        if other.is_neutral_element() {
+            self.clone()
+        } else if self.is_neutral_element() {
+            other.clone()
+        } else if other.z == C::BaseField::one() {
+            let z1_2 = self.z.pow(2u64);
+            let u2 = other.x.clone() * z1_2.clone();
+            let s2 = other.y.clone() * z1_2 * self.z.clone();
+            self.add_with_scaled_coordinates(
+                [self.x.clone(), u2, self.y.clone(), s2],
+                self.z.clone(),
+            )
+        } else {
+            let z1_2 = self.z.pow(2u64);
+            let z2_2 = other.z.pow(2u64);
+            let u1 = self.x.clone() * z2_2.clone();
+            let u2 = other.x.clone() * z1_2.clone();
+            let s1 = self.y.clone() * z2_2 * other.z.clone();
+            let s2 = other.y.clone() * z1_2 * self.z.clone();
+            self.add_with_scaled_coordinates([u1, u2, s1, s2], self.z.clone() * other.z.clone())
+        }
+    }
+
+    fn inverse(&self) -> Self {
+        -self
+    }
+}
+
+impl<C: ShortWeierstrassParams> CyclicGroup for ShortWeierstrassPoint<C> {
+    type Scalar = C::ScalarField;
+
+    /// Returns the generator of the curve given by its parameters.
+    fn generator() -> Self {
+        let (x, y) = C::generator_affine();
+        Self::new(x, y, C::BaseField::one())
+    }
+}
+
+impl<C: ShortWeierstrassParams> ConditionalSwap for ShortWeierstrassPoint<C>
+where
+    C::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u64) {
+        C::BaseField::conditional_swap(&mut a.x, &mut b.x, mask);
+        C::BaseField::conditional_swap(&mut a.y, &mut b.y, mask);
+        C::BaseField::conditional_swap(&mut a.z, &mut b.z, mask);
+    }
+}
+
+impl<C: ShortWeierstrassParams> PartialEq for ShortWeierstrassPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        // Jacobian equality relation: (x, y, z) ~ (l^2 * x, l^3 * y, l * z)
+        match (self.is_neutral_element(), other.is_neutral_element()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1_2 = self.z.pow(2u64);
+                let z2_2 = other.z.pow(2u64);
+                self.x.clone() * z2_2.clone() == other.x.clone() * z1_2.clone()
+                    && self.y.clone() * z2_2 * other.z.clone()
+                        == other.y.clone() * z1_2 * self.z.clone()
+            }
+            _ => false,
+        }
+    }
+}
+impl<C: ShortWeierstrassParams> Eq for ShortWeierstrassPoint<C> {}
+
+impl<C: ShortWeierstrassParams> ops::Neg for &ShortWeierstrassPoint<C> {
+    type Output = ShortWeierstrassPoint<C>;
+
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            x: self.x.clone(),
+            y: -self.y.clone(),
+            z: self.z.clone(),
+        }
+    }
+}
+
+impl<C: ShortWeierstrassParams> ops::Neg for ShortWeierstrassPoint<C> {
+    type Output = ShortWeierstrassPoint<C>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<59>;
+
+    // The same curve used by `EllipticCurveElement` in its tests,
+    // restricted to the base field.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Curve59;
+    impl ShortWeierstrassParams for Curve59 {
+        type BaseField = FE;
+        type ScalarField = FieldElement<5>;
+        type Cofactor = u64;
+
+        const COFACTOR: u64 = 12;
+
+        fn a() -> FE {
+            FE::new(1)
+        }
+
+        fn b() -> FE {
+            FE::new(0)
+        }
+
+        fn generator_affine() -> (FE, FE) {
+            (FE::new(35), FE::new(31))
+        }
+    }
+    type Point = ShortWeierstrassPoint<Curve59>;
+
+    #[test]
+    #[should_panic]
+    fn create_invalid_points_panicks() {
+        Point::new(FE::new(0), FE::new(1), FE::new(1));
+    }
+
+    #[test]
+    fn try_new_rejects_points_outside_the_curve() {
+        assert_eq!(
+            Point::try_new(FE::new(0), FE::new(1), FE::new(1)),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+        assert_eq!(
+            Point::from_affine(FE::new(35), FE::new(31)),
+            Ok(Point::generator())
+        );
+    }
+
+    #[test]
+    fn only_multiples_of_the_generator_are_in_the_subgroup() {
+        assert!(Point::generator().operate_with_self(3u64).is_in_subgroup());
+        // (0, 0) has order 2.
+        assert!(!Point::from_affine(FE::new(0), FE::new(0))
+            .unwrap()
+            .is_in_subgroup());
+    }
+
+    #[test]
+    fn doubling_a_point_works() {
+        let point = Point::generator().operate_with_self(2u64);
+        assert_eq!(point.to_affine(), (FE::new(25), FE::new(29)));
+    }
+
+    #[test]
+    fn generator_has_order_5() {
+        let point = Point::generator();
+        assert_ne!(point.operate_with_self(4u64), Point::neutral_element());
+        assert_eq!(point.operate_with_self(5u64), Point::neutral_element());
+    }
+
+    #[test]
+    fn results_of_the_group_operations_lie_on_the_curve() {
+        let g = Point::generator();
+        let p = g.operate_with_self(3u64);
+        let q = p.operate_with(&g).operate_with(&p);
+        for point in [p.clone(), q.clone(), p.operate_with(&q), q.double()] {
+            assert_eq!(
+                Point::defining_equation(&point.x, &point.y, &point.z),
+                FE::new(0)
+            );
+        }
+    }
+
+    #[test]
+    fn mixed_addition_matches_jacobian_addition() {
+        let g = Point::generator();
+        let p = g.operate_with_self(2u64);
+        let q = g.operate_with_self(4u64);
+        assert_ne!(q.z, FE::new(1));
+        // `q.normalize()` takes the mixed addition path.
+        assert_eq!(p.operate_with(&q.normalize()), p.operate_with(&q));
+        assert_eq!(p.operate_with(&q), g.operate_with_self(6u64));
+        assert_eq!(p.operate_with(&p), p.double());
+    }
+
+    #[test]
+    fn batch_normalize_matches_to_affine() {
+        let g = Point::generator();
+        let points: Vec<_> = (0..6u64).map(|k| g.operate_with_self(k)).collect();
+        let normalized = Point::batch_normalize(&points);
+        for (point, normalized) in points.iter().zip(normalized.iter()) {
+            if point.is_neutral_element() {
+                assert!(normalized.is_neutral_element());
+            } else {
+                assert_eq!(normalized.z, FE::new(1));
+                assert_eq!((normalized.x, normalized.y), point.to_affine());
+            }
+        }
+    }
+
+    #[test]
+    fn wnaf_and_montgomery_ladder_match_repeated_addition() {
+        let g = Point::generator();
+        let mut expected = Point::neutral_element();
+        for k in 0..12u64 {
+            assert_eq!(g.operate_with_self(k), expected);
+            let scalar = FieldElement::<5>::new(k as u128 % 5);
+            assert_eq!(g.montgomery_ladder(&scalar), expected);
+            expected = expected.operate_with(&g);
+        }
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        let g = Point::generator();
+        for k in 0..5u64 {
+            let p = g.operate_with_self(k);
+            assert_eq!(Point::from_bytes(&p.to_bytes_compressed()), Ok(p.clone()));
+            assert_eq!(Point::from_bytes(&p.to_bytes_uncompressed()), Ok(p));
+        }
+    }
+
+    #[test]
+    fn flags_take_a_byte_of_their_own_when_the_field_has_no_room() {
+        // 58 takes 6 of the 8 bits of its byte, so there is no room for three flags.
+        let g = Point::generator();
+        assert_eq!(g.to_bytes_uncompressed(), vec![0x00, 35, 31]);
+        // 31 > 59 - 31, so the sign flag is set.
+        assert_eq!(g.to_bytes_compressed(), vec![0xa0, 35]);
+        assert_eq!((-g).to_bytes_compressed(), vec![0x80, 35]);
+        assert_eq!(
+            Point::neutral_element().to_bytes_compressed(),
+            vec![0xc0, 0]
+        );
+    }
+
+    #[test]
+    fn decoding_rejects_non_canonical_encodings() {
+        for bytes in [
+            &[][..],
+            &[0x80],
+            &[0x80, 35, 31],
+            &[0x00, 35],
+            &[0x90, 35],
+            &[0x80, 59],
+            &[0x20, 35, 31],
+            &[0xc0, 1],
+            &[0xe0, 0],
+            // y = 0 has a single root, encoded without the sign flag.
+            &[0xa0, 0],
+        ] {
+            assert_eq!(
+                Point::from_bytes(bytes),
+                Err(EllipticCurveError::InvalidEncoding),
+                "{bytes:?}"
+            );
+        }
+        assert_eq!(
+            Point::from_bytes(&[0x80, 0]),
+            Point::from_affine(FE::new(0), FE::new(0))
+        );
+    }
+
+    #[test]
+    fn decoding_rejects_points_outside_the_curve() {
+        assert_eq!(
+            Point::from_bytes(&[0x00, 35, 30]),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+        // 2^3 + 2 = 10 is not a square modulo 59.
+        assert_eq!(
+            Point::from_bytes(&[0x80, 2]),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn adding_the_negation_gives_the_neutral_element() {
+        let point = Point::generator().operate_with_self(3u64);
+        assert_eq!(
+            point.operate_with(&-point.clone()),
+            Point::neutral_element()
+        );
+    }
+
+    #[test]
+    fn random_points_are_in_the_subgroup() {
+        for _ in 0..20 {
+            let point = Point::random();
+            assert!(!point.is_neutral_element());
+            assert!(point.is_in_subgroup());
+        }
+    }
+
+    #[test]
+    fn clear_cofactor_maps_every_point_to_the_subgroup() {
+        for x in 0..59 {
+            for sign in [false, true] {
+                if let Some(point) = Point::from_x(FE::new(x), sign) {
+                    assert!(point.clear_cofactor().is_in_subgroup());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hash_to_curve_is_deterministic_and_lands_in_the_subgroup() {
+        let point = Point::hash_to_curve(b"message", b"PINOCCHIO-TEST");
+        assert!(!point.is_neutral_element());
+        assert!(point.is_in_subgroup());
+        assert_eq!(point, Point::hash_to_curve(b"message", b"PINOCCHIO-TEST"));
+    }
+
+    #[test]
+    fn hash_to_curve_depends_on_the_message_and_the_tag() {
+        // The subgroup only has 4 points other than infinity, so single inputs may collide.
+        let points: Vec<_> = (0_u8..10)
+            .map(|i| Point::hash_to_curve(&[i], b"PINOCCHIO-TEST"))
+            .collect();
+        assert!(points.iter().any(|point| *point != points[0]));
+        let tagged: Vec<_> = (0_u8..10)
+            .map(|i| Point::hash_to_curve(b"message", &[i]))
+            .collect();
+        assert!(tagged.iter().any(|point| *point != tagged[0]));
+    }
+
+    #[test]
+    fn hash_to_curve_does_not_let_the_tag_run_into_the_message() {
+        // With the length after the tag, both would be "A\x01\0\0\0\0\x06\0\0\0\0\x01".
+        for counter in 0..4 {
+            assert_ne!(
+                hash_to_curve_preimage(b"\x06\0\0\0\0\x01", b"A", counter),
+                hash_to_curve_preimage(b"\x01", b"A\x01\0\0\0\0", counter)
+            );
+        }
+    }
+
+    #[test]
+    fn hash_to_curve_hashes_long_tags_first() {
+        let long_tag = [7; 256];
+        let hashed_tag = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(long_tag)
+            .finalize();
+        assert_eq!(
+            hash_to_curve_preimage(b"message", &long_tag, 0),
+            hash_to_curve_preimage(b"message", &hashed_tag, 0)
+        );
+        assert_eq!(
+            Point::hash_to_curve(b"message", &long_tag),
+            Point::hash_to_curve(b"message", &hashed_tag)
+        );
+        // Tags that fit are used as they are.
+        let tag = [7; 255];
+        assert_eq!(
+            hash_to_curve_preimage(b"", &tag, 0)[..256],
+            [&[255], &tag[..]].concat()
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops;
+
+/// Represents an unsigned integer of `64 * NUM_LIMBS` bits.
+/// The limbs are stored in little-endian order: `limbs[0]` holds the least
+/// significant 64 bits and `limbs[NUM_LIMBS - 1]` the most significant ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnsignedInteger<const NUM_LIMBS: usize> {
+    pub limbs: [u64; NUM_LIMBS],
+}
+
+pub type U128 = UnsignedInteger<2>;
+pub type U256 = UnsignedInteger<4>;
+pub type U384 = UnsignedInteger<6>;
+
+impl<const NUM_LIMBS: usize> UnsignedInteger<NUM_LIMBS> {
+    /// Creates an unsigned integer from its little-endian limbs.
+    pub const fn from_limbs(limbs: [u64; NUM_LIMBS]) -> Self {
+        Self { limbs }
+    }
+
+    pub const fn from_u64(value: u64) -> Self {
+        let mut limbs = [0u64; NUM_LIMBS];
+        limbs[0] = value;
+        Self { limbs }
+    }
+
+    /// Creates an unsigned integer from a `u128`.
+    /// Panics if `NUM_LIMBS` is 1 and `value` does not fit in 64 bits.
+    pub const fn from_u128(value: u128) -> Self {
+        let mut limbs = [0u64; NUM_LIMBS];
+        limbs[0] = value as u64;
+        if NUM_LIMBS > 1 {
+            limbs[1] = (value >> 64) as u64;
+        } else {
+            assert!(value >> 64 == 0, "The value does not fit in a single limb.");
+        }
+        Self { limbs }
+    }
+
+    /// Parses a hexadecimal string, with or without the `0x` prefix.
+    /// Panics if the string has invalid characters or does not fit in `NUM_LIMBS` limbs.
+    /// Since it is a `const fn` it can be used to declare constants.
+    pub const fn from_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        let mut start = 0;
+        if bytes.len() >= 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+            start = 2;
+        }
+        assert!(
+            bytes.len() - start <= 16 * NUM_LIMBS,
+            "The hexadecimal string does not fit in the unsigned integer."
+        );
+
+        let mut limbs = [0u64; NUM_LIMBS];
+        let mut i = bytes.len();
+        let mut position = 0;
+        while i > start {
+            i -= 1;
+            let digit = match bytes[i] {
+                b'0'..=b'9' => bytes[i] - b'0',
+                b'a'..=b'f' => bytes[i] - b'a' + 10,
+                b'A'..=b'F' => bytes[i] - b'A' + 10,
+                _ => panic!("Invalid hexadecimal character."),
+            };
+            limbs[position / 16] |= (digit as u64) << (4 * (position % 16));
+            position += 1;
+        }
+        Self { limbs }
+    }
+
+    pub const fn zero() -> Self {
+        Self {
+            limbs: [0u64; NUM_LIMBS],
+        }
+    }
+
+    pub const fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        let mut i = 0;
+        while i < NUM_LIMBS {
+            if self.limbs[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns `true` if the `i`-th bit, counting from the least significant one, is set.
+    pub const fn bit(&self, i: usize) -> bool {
+        if i >= 64 * NUM_LIMBS {
+            false
+        } else {
+            (self.limbs[i / 64] >> (i % 64)) & 1 == 1
+        }
+    }
+
+    /// Returns the minimum number of bits needed to represent `self`.
+    pub const fn bits(&self) -> usize {
+        let mut i = NUM_LIMBS;
+        while i > 0 {
+            i -= 1;
+            if self.limbs[i] != 0 {
+                return 64 * i + (64 - self.limbs[i].leading_zeros() as usize);
+            }
+        }
+        0
+    }
+
+    /// Compares `a` and `b` starting from the most significant limb.
+    pub const fn const_cmp(a: &Self, b: &Self) -> Ordering {
+        let mut i = NUM_LIMBS;
+        while i > 0 {
+            i -= 1;
+            if a.limbs[i] > b.limbs[i] {
+                return Ordering::Greater;
+            }
+            if a.limbs[i] < b.limbs[i] {
+                return Ordering::Less;
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Computes `a + b`. Returns the result modulo `2^(64 * NUM_LIMBS)`
+    /// and a flag that is `true` if the addition overflowed.
+    pub const fn add(a: &Self, b: &Self) -> (Self, bool) {
+        let mut limbs = [0u64; NUM_LIMBS];
+        let mut carry = false;
+        let mut i = 0;
+        while i < NUM_LIMBS {
+            let (sum, carry_1) = a.limbs[i].overflowing_add(b.limbs[i]);
+            let (sum, carry_2) = sum.overflowing_add(carry as u64);
+            limbs[i] = sum;
+            carry = carry_1 || carry_2;
+            i += 1;
+        }
+        (Self { limbs }, carry)
+    }
+
+    /// Computes `a - b`. Returns the result modulo `2^(64 * NUM_LIMBS)`
+    /// and a flag that is `true` if `b` was bigger than `a`.
+    pub const fn sub(a: &Self, b: &Self) -> (Self, bool) {
+        let mut limbs = [0u64; NUM_LIMBS];
+        let mut borrow = false;
+        let mut i = 0;
+        while i < NUM_LIMBS {
+            let (difference, borrow_1) = a.limbs[i].overflowing_sub(b.limbs[i]);
+            let (difference, borrow_2) = difference.overflowing_sub(borrow as u64);
+            limbs[i] = difference;
+            borrow = borrow_1 || borrow_2;
+            i += 1;
+        }
+        (Self { limbs }, borrow)
+    }
+
+    /// Computes the full product `a * b` using schoolbook multiplication.
+    /// Output: (high limbs, low limbs)
+    pub const fn mul(a: &Self, b: &Self) -> (Self, Self) {
+        let mut low = [0u64; NUM_LIMBS];
+        let mut high = [0u64; NUM_LIMBS];
+        let mut i = 0;
+        while i < NUM_LIMBS {
+            let mut carry: u128 = 0;
+            let mut j = 0;
+            while j < NUM_LIMBS {
+                let position = i + j;
+                let current = if position < NUM_LIMBS {
+                    low[position]
+                } else {
+                    high[position - NUM_LIMBS]
+                };
+                let product =
+                    (a.limbs[i] as u128) * (b.limbs[j] as u128) + (current as u128) + carry;
+                if position < NUM_LIMBS {
+                    low[position] = product as u64;
+                } else {
+                    high[position - NUM_LIMBS] = product as u64;
+                }
+                carry = product >> 64;
+                j += 1;
+            }
+            high[i] = carry as u64;
+            i += 1;
+        }
+        (Self { limbs: high }, Self { limbs: low })
+    }
+
+    /// Shifts `self` to the left by `times` bits, dropping the bits that overflow.
+    pub const fn const_shl(&self, times: usize) -> Self {
+        let mut limbs = [0u64; NUM_LIMBS];
+        let limb_shift = times / 64;
+        let bit_shift = times % 64;
+        let mut i = NUM_LIMBS;
+        while i > limb_shift {
+            i -= 1;
+            let source = i - limb_shift;
+            limbs[i] = self.limbs[source] << bit_shift;
+            if bit_shift > 0 && source > 0 {
+                limbs[i] |= self.limbs[source - 1] >> (64 - bit_shift);
+            }
+        }
+        Self { limbs }
+    }
+
+    /// Shifts `self` to the right by `times` bits.
+    pub const fn const_shr(&self, times: usize) -> Self {
+        let mut limbs = [0u64; NUM_LIMBS];
+        let limb_shift = times / 64;
+        let bit_shift = times % 64;
+        let mut i = 0;
+        while i + limb_shift < NUM_LIMBS {
+            let source = i + limb_shift;
+            limbs[i] = self.limbs[source] >> bit_shift;
+            if bit_shift > 0 && source + 1 < NUM_LIMBS {
+                limbs[i] |= self.limbs[source + 1] << (64 - bit_shift);
+            }
+            i += 1;
+        }
+        Self { limbs }
+    }
+}
+
+impl<const NUM_LIMBS: usize> From<u64> for UnsignedInteger<NUM_LIMBS> {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+impl<const NUM_LIMBS: usize> From<u16> for UnsignedInteger<NUM_LIMBS> {
+    fn from(value: u16) -> Self {
+        Self::from_u64(value.into())
+    }
+}
+
+impl<const NUM_LIMBS: usize> PartialOrd for UnsignedInteger<NUM_LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const NUM_LIMBS: usize> Ord for UnsignedInteger<NUM_LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Self::const_cmp(self, other)
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::Add for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (result, overflow) = Self::add(&self, &other);
+        assert!(!overflow, "UnsignedInteger addition overflow.");
+        result
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::Sub for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let (result, underflow) = Self::sub(&self, &other);
+        assert!(!underflow, "UnsignedInteger subtraction underflow.");
+        result
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::Mul for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (high, low) = Self::mul(&self, &other);
+        assert!(high.is_zero(), "UnsignedInteger multiplication overflow.");
+        low
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::Shl<usize> for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn shl(self, times: usize) -> Self {
+        self.const_shl(times)
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::Shr<usize> for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn shr(self, times: usize) -> Self {
+        self.const_shr(times)
+    }
+}
+
+impl<const NUM_LIMBS: usize> ops::BitAnd for UnsignedInteger<NUM_LIMBS> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        let mut limbs = self.limbs;
+        for (limb, other_limb) in limbs.iter_mut().zip(other.limbs) {
+            *limb &= other_limb;
+        }
+        Self { limbs }
+    }
+}
+
+/// Unsigned integer types that can be used as exponents
+/// in square-and-multiply algorithms.
+pub trait IsUnsignedInteger:
+    ops::Shr<usize, Output = Self>
+    + ops::BitAnd<Output = Self>
+    + Eq
+    + Ord
+    + From<u16>
+    + Copy
+    + Debug
+    + Send
+    + Sync
+{
+}
+
+impl IsUnsignedInteger for u16 {}
+impl IsUnsignedInteger for u32 {}
+impl IsUnsignedInteger for u64 {}
+impl IsUnsignedInteger for u128 {}
+impl IsUnsignedInteger for usize {}
+impl<const NUM_LIMBS: usize> IsUnsignedInteger for UnsignedInteger<NUM_LIMBS> {}
+
+/// Returns the minimum number of bits needed to represent `k`.
+pub fn bit_length<U: IsUnsignedInteger>(mut k: U) -> usize {
+    let zero = U::from(0);
+    let mut bits = 0;
+    while k > zero {
+        k = k >> 1;
+        bits += 1;
+    }
+    bits
+}
+
+/// Returns the `width` bits of `k` starting from bit `start`.
+/// `start` must be smaller than the bit length of `U`.
+pub fn window_value<U: IsUnsignedInteger>(k: U, start: usize, width: usize) -> usize {
+    let one = U::from(1);
+    let mut shifted = k >> start;
+    let mut value = 0;
+    for i in 0..width {
+        if shifted & one == one {
+            value |= 1 << i;
+        }
+        shifted = shifted >> 1;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_of_1_is_1() {
+        assert_eq!(U256::from_hex("0x1"), U256::from_u64(1));
+    }
+
+    #[test]
+    fn from_hex_reads_limbs_in_little_endian_order() {
+        let a =
+            U256::from_hex("0x0000000000000004000000000000000300000000000000020000000000000001");
+        assert_eq!(a.limbs, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_hex_without_prefix_works() {
+        assert_eq!(
+            U128::from_hex("ffffffffffffffffff"),
+            U128::from_limbs([u64::MAX, 0xff])
+        );
+    }
+
+    #[test]
+    fn from_u128_splits_in_two_limbs() {
+        let a = U256::from_u128(u128::MAX);
+        assert_eq!(a.limbs, [u64::MAX, u64::MAX, 0, 0]);
+    }
+
+    #[test]
+    fn adding_1_to_max_limb_carries() {
+        let a = U256::from_u64(u64::MAX);
+        assert_eq!(a + U256::one(), U256::from_limbs([0, 1, 0, 0]));
+    }
+
+    #[test]
+    fn adding_1_to_max_value_overflows() {
+        let a = U128::from_limbs([u64::MAX, u64::MAX]);
+        let (result, overflow) = U128::add(&a, &U128::one());
+        assert_eq!(result, U128::zero());
+        assert!(overflow);
+    }
+
+    #[test]
+    fn subtracting_1_from_2_to_the_64_borrows() {
+        let a = U256::from_limbs([0, 1, 0, 0]);
+        assert_eq!(a - U256::one(), U256::from_u64(u64::MAX));
+    }
+
+    #[test]
+    fn subtracting_1_from_0_underflows() {
+        let (result, underflow) = U128::sub(&U128::zero(), &U128::one());
+        assert_eq!(result, U128::from_limbs([u64::MAX, u64::MAX]));
+        assert!(underflow);
+    }
+
+    #[test]
+    fn mul_of_max_limbs_matches_u128_multiplication() {
+        let a = U128::from_u64(u64::MAX);
+        let expected = U128::from_u128((u64::MAX as u128) * (u64::MAX as u128));
+        assert_eq!(a * a, expected);
+    }
+
+    #[test]
+    fn full_mul_returns_high_and_low_parts() {
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        let a = U128::from_limbs([u64::MAX, u64::MAX]);
+        let (high, low) = U128::mul(&a, &a);
+        assert_eq!(low, U128::one());
+        assert_eq!(high, U128::from_limbs([u64::MAX - 1, u64::MAX]));
+    }
+
+    #[test]
+    fn shl_and_shr_across_limbs() {
+        let a = U256::from_u64(0b101);
+        let shifted = a << 126;
+        assert_eq!(shifted.limbs, [0, 1 << 62, 1, 0]);
+        assert_eq!(shifted >> 126, a);
+    }
+
+    #[test]
+    fn shr_by_multiple_of_64() {
+        let a = U256::from_limbs([1, 2, 3, 4]);
+        assert_eq!(a >> 128, U256::from_limbs([3, 4, 0, 0]));
+    }
+
+    #[test]
+    fn comparison_starts_with_most_significant_limb() {
+        let a = U256::from_limbs([u64::MAX, 0, 0, 0]);
+        let b = U256::from_limbs([0, 1, 0, 0]);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn bits_of_2_to_the_100_is_101() {
+        let a = U256::one() << 100;
+        assert_eq!(a.bits(), 101);
+        assert!(a.bit(100));
+        assert!(!a.bit(99));
+    }
+
+    #[test]
+    fn bits_of_zero_is_zero() {
+        assert_eq!(U256::zero().bits(), 0);
+    }
+
+    #[test]
+    fn window_value_reads_bits_from_the_given_position() {
+        assert_eq!(window_value(0b1011_0110u64, 2, 3), 0b101);
+        assert_eq!(window_value(0b1011_0110u64, 5, 4), 0b101);
+    }
+}
@@ -0,0 +1,62 @@
+//! Helpers to spread independent computations across threads.
+//!
+//! Threads are only used when the crate is compiled with the `parallel`
+//! feature. Otherwise every helper runs sequentially on the calling thread.
+//! In both cases the results come out in the same order and are computed
+//! in the same way, so they are identical.
+
+/// Returns the number of threads used by the helpers of this module.
+/// It is the available parallelism of the machine when the `parallel`
+/// feature is enabled, and 1 otherwise.
+pub fn number_of_threads() -> usize {
+    if cfg!(feature = "parallel") {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        1
+    }
+}
+
+/// Applies `f` to every element of `items` and returns the results in order.
+/// The elements are split into contiguous chunks, one per thread.
+pub fn map<I, O, F>(items: &[I], f: F) -> Vec<O>
+where
+    I: Sync,
+    O: Send,
+    F: Fn(&I) -> O + Sync,
+{
+    let number_of_threads = number_of_threads().min(items.len());
+    if number_of_threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(number_of_threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<O>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("A worker thread panicked."))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_keeps_the_order_of_the_items() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|x| x * x).collect();
+        assert_eq!(map(&items, |x| x * x), expected);
+    }
+
+    #[test]
+    fn map_of_empty_slice_is_empty() {
+        let items: [u64; 0] = [];
+        assert!(map(&items, |x| x + 1).is_empty());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod prover;
 pub mod setup;
+#[cfg(any(test, feature = "symbolic"))]
+pub mod symbolic;
 pub mod verifier;
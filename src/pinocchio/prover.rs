@@ -1,31 +1,28 @@
-use super::super::config::ORDER_R;
 use crate::circuits::qap::QuadraticArithmeticProgram as QAP;
-use crate::math::cyclic_group::CyclicBilinearGroup;
-use crate::math::field_element::FieldElement;
+use crate::math::cyclic_group::PairingEngine;
 use crate::math::msm::msm;
 
 use super::setup::EvaluationKey;
 
-type FE = FieldElement<ORDER_R>;
-
 /// Pinocchio's proof
 /// Using the notation of Pinocchio's paper, these are
 /// the hidings of v_{mid}(s), w_{mid}(s), y_{mid}(s), h(s)
 /// and the "redundant" hidings for the consistency checks
 /// of the verifier.
+/// The hidings of w_{mid}(s) live in G2, all the others in G1.
 /// The polynomials v, w and y are the polynomials from the QAP
 /// The polynomial h is equal to (vw - y) / t, where t is the
 /// target polynomial of the QAP.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Proof<T: CyclicBilinearGroup> {
-    pub g_vs: T,
-    pub g_ws: T,
-    pub g_ys: T,
-    pub g_hs: T,
-    pub g_alpha_vs: T,
-    pub g_alpha_ws: T,
-    pub g_alpha_ys: T,
-    pub g_beta_vwy: T,
+pub struct Proof<E: PairingEngine> {
+    pub g_vs: E::G1,
+    pub g_ws: E::G2,
+    pub g_ys: E::G1,
+    pub g_hs: E::G1,
+    pub g_alpha_vs: E::G1,
+    pub g_alpha_ws: E::G2,
+    pub g_alpha_ys: E::G1,
+    pub g_beta_vwy: E::G1,
 }
 
 /// Generates a proof.
@@ -35,11 +32,11 @@ pub struct Proof<T: CyclicBilinearGroup> {
 /// to the particular execution instance. These values are
 /// the ones denoted `c_i` in the paper. They include all
 /// inputs and outputs.
-pub fn generate_proof<T: CyclicBilinearGroup>(
-    evaluation_key: &EvaluationKey<T>,
-    qap: &QAP,
-    qap_c_coefficients: &[FE],
-) -> Proof<T> {
+pub fn generate_proof<E: PairingEngine>(
+    evaluation_key: &EvaluationKey<E>,
+    qap: &QAP<E::Scalar>,
+    qap_c_coefficients: &[E::Scalar],
+) -> Proof<E> {
     let c_mid = &qap_c_coefficients
         [qap.number_of_inputs..(qap_c_coefficients.len() - qap.number_of_outputs)];
 
@@ -62,10 +59,16 @@ pub fn generate_proof<T: CyclicBilinearGroup>(
 
 #[cfg(test)]
 mod tests {
-    use crate::math::elliptic_curve::EllipticCurveElement;
-    use crate::math::{cyclic_group::CyclicBilinearGroup, polynomial::Polynomial};
+    use crate::config::{DefaultCurve, ORDER_R};
+    use crate::math::cyclic_group::{CyclicGroup, Group};
+    use crate::math::elliptic_curve;
+    use crate::math::polynomial::Polynomial;
 
     use super::*;
+    use crate::math::field_element::FieldElement;
+
+    type FE = FieldElement<ORDER_R>;
+    type EllipticCurveElement = elliptic_curve::EllipticCurveElement<DefaultCurve>;
 
     // This test runs the proof algorithms with some easy inputs
     // to check operations are correct
@@ -95,7 +98,7 @@ mod tests {
         };
         // Dummy evaluation key assuming
         // (s, r_v, r_w, alpha_v, alpha_w, alpha_y, beta, gamma) = (1, 1, 1, 2, 2, 2, 3, 1)
-        let evaluation_key = EvaluationKey {
+        let evaluation_key: EvaluationKey<FE> = EvaluationKey {
             gv_ks: vec![FE::new(2), FE::new(2)],
             gw_ks: vec![FE::new(2), FE::new(2)],
             gy_ks: vec![FE::new(2), FE::new(2)],
@@ -161,30 +164,33 @@ mod tests {
 
         let g = EllipticCurveElement::generator();
 
-        let evaluation_key = EvaluationKey {
-            gv_ks: vec![g.operate_with_self(2), g.operate_with_self(2)],
-            gw_ks: vec![g.operate_with_self(2), g.operate_with_self(2)],
-            gy_ks: vec![g.operate_with_self(2), g.operate_with_self(2)],
-            gv_alphaks: vec![g.operate_with_self(4), g.operate_with_self(4)],
-            gw_alphaks: vec![g.operate_with_self(4), g.operate_with_self(4)],
-            gy_alphaks: vec![g.operate_with_self(4), g.operate_with_self(4)],
+        let evaluation_key: EvaluationKey<EllipticCurveElement> = EvaluationKey {
+            gv_ks: vec![g.operate_with_self(2u64), g.operate_with_self(2u64)],
+            gw_ks: vec![g.operate_with_self(2u64), g.operate_with_self(2u64)],
+            gy_ks: vec![g.operate_with_self(2u64), g.operate_with_self(2u64)],
+            gv_alphaks: vec![g.operate_with_self(4u64), g.operate_with_self(4u64)],
+            gw_alphaks: vec![g.operate_with_self(4u64), g.operate_with_self(4u64)],
+            gy_alphaks: vec![g.operate_with_self(4u64), g.operate_with_self(4u64)],
             g_s_i: vec![g.clone(), g.clone()],
-            g_beta: vec![g.operate_with_self(18), g.operate_with_self(18)],
+            g_beta: vec![g.operate_with_self(18u64), g.operate_with_self(18u64)],
         };
 
         let c_coefficients = vec![FE::new(3), FE::new(2), FE::new(1), FE::new(1)];
 
         let proof = generate_proof(&evaluation_key, &easy_qap, &c_coefficients);
 
-        assert_eq!(proof.g_vs, g.operate_with_self(6));
-        assert_eq!(proof.g_ws, g.operate_with_self(6));
-        assert_eq!(proof.g_ys, g.operate_with_self(6));
-        assert_eq!(proof.g_alpha_vs, g.operate_with_self(2 * 6));
-        assert_eq!(proof.g_alpha_ws, g.operate_with_self(2 * 6));
-        assert_eq!(proof.g_alpha_ws, g.operate_with_self(2 * 6));
+        assert_eq!(proof.g_vs, g.operate_with_self(6u64));
+        assert_eq!(proof.g_ws, g.operate_with_self(6u64));
+        assert_eq!(proof.g_ys, g.operate_with_self(6u64));
+        assert_eq!(proof.g_alpha_vs, g.operate_with_self(2u64 * 6));
+        assert_eq!(proof.g_alpha_ws, g.operate_with_self(2u64 * 6));
+        assert_eq!(proof.g_alpha_ws, g.operate_with_self(2u64 * 6));
 
-        assert_eq!(proof.g_hs, g.operate_with_self(120));
+        assert_eq!(proof.g_hs, g.operate_with_self(120u64));
 
-        assert_eq!(proof.g_beta_vwy, g.operate_with_self(3 * 6 + 3 * 6 + 3 * 6));
+        assert_eq!(
+            proof.g_beta_vwy,
+            g.operate_with_self(3u64 * 6 + 3 * 6 + 3 * 6)
+        );
     }
 }
@@ -1,65 +1,65 @@
-use super::super::config::ORDER_R;
 use crate::circuits::qap::QuadraticArithmeticProgram as QAP;
 use crate::math;
-use math::cyclic_group::CyclicBilinearGroup;
-use math::field_element::FieldElement;
+use crate::parallel;
+use math::cyclic_group::{CyclicGroup, PairingEngine};
+use math::field::{ConditionalSwap, Field, PrimeField};
+use math::fixed_base::FixedBaseTable;
+use math::polynomial::Polynomial;
+use std::ops;
 
-pub type FE = FieldElement<ORDER_R>;
-
-/// Evaluation key for Pinocchio
+/// Evaluation key for Pinocchio.
+/// The hidings of the w polynomials live in G2, all the others in G1.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct EvaluationKey<T: CyclicBilinearGroup> {
-    pub gv_ks: Vec<T>,
-    pub gw_ks: Vec<T>,
-    pub gy_ks: Vec<T>,
-    pub gv_alphaks: Vec<T>,
-    pub gw_alphaks: Vec<T>,
-    pub gy_alphaks: Vec<T>,
-    pub g_s_i: Vec<T>,
-    pub g_beta: Vec<T>,
+pub struct EvaluationKey<E: PairingEngine> {
+    pub gv_ks: Vec<E::G1>,
+    pub gw_ks: Vec<E::G2>,
+    pub gy_ks: Vec<E::G1>,
+    pub gv_alphaks: Vec<E::G1>,
+    pub gw_alphaks: Vec<E::G2>,
+    pub gy_alphaks: Vec<E::G1>,
+    pub g_s_i: Vec<E::G1>,
+    pub g_beta: Vec<E::G1>,
 }
-/// Verifying key for Pinocchio
+/// Verifying key for Pinocchio.
+/// Each hiding lives in the group that its pairing partner in
+/// the verifier checks does not, so that every pairing is e(G1, G2).
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct VerificationKey<T: CyclicBilinearGroup> {
-    pub g_1: T,
-    pub g_alpha_v: T,
-    pub g_alpha_w: T,
-    pub g_alpha_y: T,
-    pub g_gamma: T,
-    pub g_beta_gamma: T,
-    pub gy_target_on_s: T,
-    pub gv_ks: Vec<T>,
-    pub gw_ks: Vec<T>,
-    pub gy_ks: Vec<T>,
+pub struct VerificationKey<E: PairingEngine> {
+    pub g_1: E::G1,
+    pub g_2: E::G2,
+    pub g_alpha_v: E::G2,
+    pub g_alpha_w: E::G1,
+    pub g_alpha_y: E::G2,
+    pub g_gamma: E::G2,
+    pub g1_beta_gamma: E::G1,
+    pub g2_beta_gamma: E::G2,
+    pub gy_target_on_s: E::G2,
+    pub gv_ks: Vec<E::G1>,
+    pub gw_ks: Vec<E::G2>,
+    pub gy_ks: Vec<E::G1>,
 }
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct ToxicWaste {
-    s: FE,
-    alpha_v: FE,
-    alpha_w: FE,
-    alpha_y: FE,
-    beta: FE,
-    rv: FE,
-    rw: FE,
-    gamma: FE,
+pub struct ToxicWaste<F> {
+    s: F,
+    alpha_v: F,
+    alpha_w: F,
+    alpha_y: F,
+    beta: F,
+    rv: F,
+    rw: F,
+    gamma: F,
 }
 
-impl ToxicWaste {
-    pub fn ry(self) -> FE {
-        self.rv * self.rw
+impl<F> ToxicWaste<F> {
+    pub fn ry(&self) -> F
+    where
+        F: Clone + ops::Mul<Output = F>,
+    {
+        self.rv.clone() * self.rw.clone()
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        s: FE,
-        alpha_v: FE,
-        alpha_w: FE,
-        alpha_y: FE,
-        beta: FE,
-        rv: FE,
-        rw: FE,
-        gamma: FE,
-    ) -> Self {
+    pub fn new(s: F, alpha_v: F, alpha_w: F, alpha_y: F, beta: F, rv: F, rw: F, gamma: F) -> Self {
         Self {
             s,
             alpha_v,
@@ -71,151 +71,180 @@ impl ToxicWaste {
             gamma,
         }
     }
+}
 
+impl<F: PrimeField> ToxicWaste<F> {
     pub fn sample() -> Self {
         Self {
-            s: FE::random(),
-            alpha_v: FE::random(),
-            alpha_w: FE::random(),
-            alpha_y: FE::random(),
-            beta: FE::random(),
-            rv: FE::random(),
-            rw: FE::random(),
-            gamma: FE::random(),
+            s: F::random(),
+            alpha_v: F::random(),
+            alpha_w: F::random(),
+            alpha_y: F::random(),
+            beta: F::random(),
+            rv: F::random(),
+            rw: F::random(),
+            gamma: F::random(),
         }
     }
 }
 
-fn generate_verification_key<T: CyclicBilinearGroup>(
-    qap: &QAP,
-    toxic_waste: &ToxicWaste,
-    generator: &T,
-) -> VerificationKey<T> {
-    let s = toxic_waste.s;
-    let alpha_v = toxic_waste.alpha_v;
-    let alpha_w = toxic_waste.alpha_w;
-    let alpha_y = toxic_waste.alpha_y;
-    let beta = toxic_waste.beta;
-    let rv = toxic_waste.rv;
-    let rw = toxic_waste.rw;
-    let gamma = toxic_waste.gamma;
-    let ry = toxic_waste.ry();
-
-    let g = generator;
-
-    let vector_capacity = qap.number_of_inputs + qap.number_of_inputs + 1;
-    let mut gv_ks_io: Vec<T> = Vec::with_capacity(vector_capacity);
-    let mut gw_ks_io: Vec<T> = Vec::with_capacity(vector_capacity);
-    let mut gy_ks_io: Vec<T> = Vec::with_capacity(vector_capacity);
-
-    gv_ks_io.push(g.operate_with_self((rv * qap.v0().evaluate(s)).representative()));
-    gw_ks_io.push(g.operate_with_self((rw * qap.w0().evaluate(s)).representative()));
-    gy_ks_io.push(g.operate_with_self((ry * qap.y0().evaluate(s)).representative()));
-
-    for k in 0..qap.v_input().len() {
-        gv_ks_io.push(g.operate_with_self((rv * qap.v_input()[k].evaluate(s)).representative()));
-        gw_ks_io.push(g.operate_with_self((rw * qap.w_input()[k].evaluate(s)).representative()));
-        gy_ks_io.push(g.operate_with_self((ry * qap.y_input()[k].evaluate(s)).representative()));
-    }
+/// Arithmetic that the key generation needs from the elements of the toxic waste.
+/// Besides scalars, symbolic expressions of the toxic waste implement it.
+pub(crate) trait ToxicWasteValue:
+    Clone + Send + Sync + ops::Add<Output = Self> + ops::Mul<Output = Self>
+{
+}
 
-    for k in 0..qap.v_output().len() {
-        gv_ks_io.push(g.operate_with_self((rv * qap.v_output()[k].evaluate(s)).representative()));
-        gw_ks_io.push(g.operate_with_self((rw * qap.w_output()[k].evaluate(s)).representative()));
-        gy_ks_io.push(g.operate_with_self((ry * qap.y_output()[k].evaluate(s)).representative()));
-    }
+impl<T: Clone + Send + Sync + ops::Add<Output = T> + ops::Mul<Output = T>> ToxicWasteValue for T {}
+
+/// Evaluates `p` at `x` with Horner's rule.
+fn evaluate<F: Field, T: ToxicWasteValue + From<F>>(p: &Polynomial<F>, x: &T) -> T {
+    p.coefficients()
+        .iter()
+        .rev()
+        .fold(T::from(F::zero()), |acc, c| {
+            acc * x.clone() + T::from(c.clone())
+        })
+}
+
+/// Hides `values` with `hide`, spreading the scalar multiplications across threads.
+fn hide_all<T: Sync, G: Send>(hide: &(impl Fn(&T) -> G + Sync), values: &[T]) -> Vec<G> {
+    parallel::map(values, hide)
+}
+
+/// Returns the polynomials of the constant wire, the inputs and the outputs, in this order.
+fn io_polynomials<'a, F: Field>(
+    p0: &'a Polynomial<F>,
+    inputs: &'a [Polynomial<F>],
+    outputs: &'a [Polynomial<F>],
+) -> Vec<&'a Polynomial<F>> {
+    std::iter::once(p0).chain(inputs).chain(outputs).collect()
+}
+
+fn generate_verification_key<E: PairingEngine, T: ToxicWasteValue + From<E::Scalar>>(
+    qap: &QAP<E::Scalar>,
+    toxic_waste: &ToxicWaste<T>,
+    hide1: &(impl Fn(&T) -> E::G1 + Sync),
+    hide2: &(impl Fn(&T) -> E::G2 + Sync),
+) -> VerificationKey<E> {
+    let s = &toxic_waste.s;
+    let rv = &toxic_waste.rv;
+    let rw = &toxic_waste.rw;
+    let ry = &toxic_waste.ry();
+
+    let vs_io = io_polynomials(qap.v0(), qap.v_input(), qap.v_output());
+    let ws_io = io_polynomials(qap.w0(), qap.w_input(), qap.w_output());
+    let ys_io = io_polynomials(qap.y0(), qap.y_input(), qap.y_output());
+
+    let rv_vs: Vec<_> = parallel::map(&vs_io, |v| rv.clone() * evaluate(v, s));
+    let rw_ws: Vec<_> = parallel::map(&ws_io, |w| rw.clone() * evaluate(w, s));
+    let ry_ys: Vec<_> = parallel::map(&ys_io, |y| ry.clone() * evaluate(y, s));
+    let beta_gamma = toxic_waste.beta.clone() * toxic_waste.gamma.clone();
 
     VerificationKey {
-        g_1: g.clone(),
-        g_alpha_v: g.operate_with_self(alpha_v.representative()),
-        g_alpha_w: g.operate_with_self(alpha_w.representative()),
-        g_alpha_y: g.operate_with_self(alpha_y.representative()),
-        g_gamma: g.operate_with_self(gamma.representative()),
-        g_beta_gamma: g.operate_with_self((beta * gamma).representative()),
-        gy_target_on_s: g.operate_with_self((ry * qap.target.evaluate(s)).representative()),
-        gv_ks: gv_ks_io,
-        gw_ks: gw_ks_io,
-        gy_ks: gy_ks_io,
+        g_1: E::G1::generator(),
+        g_2: E::G2::generator(),
+        g_alpha_v: hide2(&toxic_waste.alpha_v),
+        g_alpha_w: hide1(&toxic_waste.alpha_w),
+        g_alpha_y: hide2(&toxic_waste.alpha_y),
+        g_gamma: hide2(&toxic_waste.gamma),
+        g1_beta_gamma: hide1(&beta_gamma),
+        g2_beta_gamma: hide2(&beta_gamma),
+        gy_target_on_s: hide2(&(ry.clone() * evaluate(&qap.target, s))),
+        gv_ks: hide_all(hide1, &rv_vs),
+        gw_ks: hide_all(hide2, &rw_ws),
+        gy_ks: hide_all(hide1, &ry_ys),
     }
 }
 
-fn generate_evaluation_key<T: CyclicBilinearGroup>(
-    qap: &QAP,
-    toxic_waste: &ToxicWaste,
-    generator: &T,
-) -> EvaluationKey<T> {
+fn generate_evaluation_key<E: PairingEngine, T: ToxicWasteValue + From<E::Scalar>>(
+    qap: &QAP<E::Scalar>,
+    toxic_waste: &ToxicWaste<T>,
+    hide1: &(impl Fn(&T) -> E::G1 + Sync),
+    hide2: &(impl Fn(&T) -> E::G2 + Sync),
+) -> EvaluationKey<E> {
     let (vs_mid, ws_mid, ys_mid) = (qap.v_mid(), qap.w_mid(), qap.y_mid());
 
-    let s = toxic_waste.s;
-    let alpha_v = toxic_waste.alpha_v;
-    let alpha_w = toxic_waste.alpha_w;
-    let alpha_y = toxic_waste.alpha_y;
-    let beta = toxic_waste.beta;
-    let rv = toxic_waste.rv;
-    let rw = toxic_waste.rw;
-    let ry = toxic_waste.ry();
-
-    let g = generator;
-
-    let degree = qap.target.degree();
-
-    let mut gv_ks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut gw_ks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut gy_ks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut gv_alphaks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut gw_alphaks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut gy_alphaks_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    let mut g_beta_mid: Vec<T> = Vec::with_capacity(vs_mid.len());
-    // g_s_i is the only paramater to depend on the degree of the qap
-    // This is an upper bound, it could be smaller
-    let mut g_s_i: Vec<T> = Vec::with_capacity(degree);
-
-    // Set evaluation keys for each of their respective k mid element
-    for k in 0..vs_mid.len() {
-        gv_ks_mid.push(g.operate_with_self((rv * vs_mid[k].evaluate(s)).representative()));
-        gw_ks_mid.push(g.operate_with_self((rw * ws_mid[k].evaluate(s)).representative()));
-        gy_ks_mid.push(g.operate_with_self((ry * ys_mid[k].evaluate(s)).representative()));
-        gv_alphaks_mid
-            .push(g.operate_with_self((ry * alpha_v * vs_mid[k].evaluate(s)).representative()));
-        gw_alphaks_mid
-            .push(g.operate_with_self((rw * alpha_w * ws_mid[k].evaluate(s)).representative()));
-        gy_alphaks_mid
-            .push(g.operate_with_self((ry * alpha_y * ys_mid[k].evaluate(s)).representative()));
-        g_beta_mid.push(
-            g.operate_with_self(
-                (rv * beta * vs_mid[k].evaluate(s)
-                    + rw * beta * ws_mid[k].evaluate(s)
-                    + ry * beta * ys_mid[k].evaluate(s))
-                .representative(),
-            ),
-        )
-    }
+    let s = &toxic_waste.s;
+    let alpha_v = &toxic_waste.alpha_v;
+    let alpha_w = &toxic_waste.alpha_w;
+    let alpha_y = &toxic_waste.alpha_y;
+    let beta = &toxic_waste.beta;
+    let rv = &toxic_waste.rv;
+    let rw = &toxic_waste.rw;
+    let ry = &toxic_waste.ry();
 
-    for i in 0..qap.target.degree() {
-        // This unwrap would only fail in an OS
-        // with 256 bits pointer, which doesn't exist
-        g_s_i.push(g.operate_with_self(s.pow(i.try_into().unwrap()).representative()));
-    }
+    // Evaluations at s of the polynomials of each k mid element
+    let vs: Vec<_> = parallel::map(vs_mid, |v| evaluate(v, s));
+    let ws: Vec<_> = parallel::map(ws_mid, |w| evaluate(w, s));
+    let ys: Vec<_> = parallel::map(ys_mid, |y| evaluate(y, s));
+
+    let scale = |factor: T, evaluations: &[T]| -> Vec<T> {
+        evaluations
+            .iter()
+            .map(|e| factor.clone() * e.clone())
+            .collect()
+    };
+    let betas: Vec<_> = (0..vs.len())
+        .map(|k| {
+            rv.clone() * beta.clone() * vs[k].clone()
+                + rw.clone() * beta.clone() * ws[k].clone()
+                + ry.clone() * beta.clone() * ys[k].clone()
+        })
+        .collect();
+    // g_s_i is the only paramater to depend on the degree of the qap
+    let powers_of_s: Vec<_> = std::iter::successors(Some(T::from(E::Scalar::one())), |p| {
+        Some(p.clone() * s.clone())
+    })
+    .take(qap.target.degree())
+    .collect();
 
     EvaluationKey {
-        gv_ks: gv_ks_mid,
-        gw_ks: gw_ks_mid,
-        gy_ks: gy_ks_mid,
-        gv_alphaks: gv_alphaks_mid,
-        gw_alphaks: gw_alphaks_mid,
-        gy_alphaks: gy_alphaks_mid,
-        g_s_i,
-        g_beta: g_beta_mid,
+        gv_ks: hide_all(hide1, &scale(rv.clone(), &vs)),
+        gw_ks: hide_all(hide2, &scale(rw.clone(), &ws)),
+        gy_ks: hide_all(hide1, &scale(ry.clone(), &ys)),
+        gv_alphaks: hide_all(hide1, &scale(rv.clone() * alpha_v.clone(), &vs)),
+        gw_alphaks: hide_all(hide2, &scale(rw.clone() * alpha_w.clone(), &ws)),
+        gy_alphaks: hide_all(hide1, &scale(ry.clone() * alpha_y.clone(), &ys)),
+        g_s_i: hide_all(hide1, &powers_of_s),
+        g_beta: hide_all(hide1, &betas),
     }
 }
 
-pub fn setup<T: CyclicBilinearGroup>(
-    qap: &QAP,
-    toxic_waste: &ToxicWaste,
-) -> (EvaluationKey<T>, VerificationKey<T>) {
-    let generator = T::generator();
+/// Generates both keys, hiding the values computed from the toxic waste
+/// in G1 with `hide1` and in G2 with `hide2`.
+pub(crate) fn generate_keys<E: PairingEngine, T: ToxicWasteValue + From<E::Scalar>>(
+    qap: &QAP<E::Scalar>,
+    toxic_waste: &ToxicWaste<T>,
+    hide1: impl Fn(&T) -> E::G1 + Sync,
+    hide2: impl Fn(&T) -> E::G2 + Sync,
+) -> (EvaluationKey<E>, VerificationKey<E>) {
     (
-        generate_evaluation_key(qap, toxic_waste, &generator),
-        generate_verification_key(qap, toxic_waste, &generator),
+        generate_evaluation_key::<E, T>(qap, toxic_waste, &hide1, &hide2),
+        generate_verification_key::<E, T>(qap, toxic_waste, &hide1, &hide2),
+    )
+}
+
+pub fn setup<E: PairingEngine>(
+    qap: &QAP<E::Scalar>,
+    toxic_waste: &ToxicWaste<E::Scalar>,
+) -> (EvaluationKey<E>, VerificationKey<E>)
+where
+    E::G1: ConditionalSwap,
+    E::G2: ConditionalSwap,
+{
+    // Every hiding is a multiple of one of the two generators, so their
+    // multiples are precomputed once. This is an upper bound on the number
+    // of hidings in each group. The toxic waste is secret, so the tables are
+    // read in a way that does not depend on it.
+    let number_of_hidings = 6 * qap.vs.len() + qap.target.degree() + 6;
+    let g1 = FixedBaseTable::new(&E::G1::generator(), number_of_hidings);
+    let g2 = FixedBaseTable::new(&E::G2::generator(), number_of_hidings);
+    generate_keys::<E, E::Scalar>(
+        qap,
+        toxic_waste,
+        |value| g1.operate_with_secret_scalar(value),
+        |value| g2.operate_with_secret_scalar(value),
     )
 }
 
@@ -224,8 +253,15 @@ mod tests {
     use super::*;
     use super::{setup, ToxicWaste};
     use crate::circuits::test_utils::new_test_qap;
+    use crate::config::ORDER_R;
+    use crate::math::cyclic_group::CyclicGroup;
+    use crate::math::cyclic_group::Group;
+    use crate::math::field_element::FieldElement;
+    use crate::pinocchio::symbolic::{self, Symbolic, Variable};
+
+    type FE = FieldElement<ORDER_R>;
 
-    fn identity_toxic_waste() -> ToxicWaste {
+    fn identity_toxic_waste() -> ToxicWaste<FE> {
         ToxicWaste {
             s: FE::new(1),
             alpha_v: FE::new(1),
@@ -255,9 +291,9 @@ mod tests {
     // with the given values of the eval key
     #[test]
     fn eval_key_returns_appropiate_values() {
-        let r5 = FE::new(2);
+        let s = FE::new(2);
         let tw = ToxicWaste {
-            s: FE::new(2),
+            s,
             alpha_v: FE::new(2),
             alpha_w: FE::new(2),
             alpha_y: FE::new(2),
@@ -267,64 +303,52 @@ mod tests {
             gamma: FE::new(1),
         };
 
-        let g = FE::generator();
-        let test_circuit = new_test_qap();
+        // The mid v polynomial of the test circuit is zero, which hides the factors
+        // of the v keys. Reusing its w polynomials as vs gives v_mid(s) = 2 instead.
+        let test_qap = new_test_qap::<FE>();
+        let test_circuit = QAP::new(
+            test_qap.ws.clone(),
+            test_qap.ws,
+            test_qap.ys,
+            test_qap.target,
+            4,
+            1,
+        )
+        .unwrap();
+        let v = test_circuit.v_mid()[0].evaluate(s);
+        let w = test_circuit.w_mid()[0].evaluate(s);
+        let y = test_circuit.y_mid()[0].evaluate(s);
+        assert_ne!(v, FE::zero());
+        assert_ne!(y, FE::zero());
 
+        let g = FE::generator();
         let (eval_key, _): (EvaluationKey<FE>, VerificationKey<FE>) = setup(&test_circuit, &tw);
 
-        // These keys should be the same evaluation * rv, which is two
-        assert_eq!(
-            eval_key.gv_ks[0],
-            g.operate_with_self(
-                (test_circuit.v_mid()[0].evaluate(r5) * FE::new(2)).representative()
-            )
-        );
-        assert_eq!(
-            eval_key.gw_ks[0],
-            g.operate_with_self(
-                (test_circuit.w_mid()[0].evaluate(r5) * FE::new(2)).representative()
-            )
-        );
-        // These keys should be the same evaluation * ys, which is two
-        // Since the whole thing is 0
-        assert_eq!(
-            eval_key.gy_ks[0],
-            g.operate_with_self(
-                (test_circuit.y_mid()[0].evaluate(r5) * FE::new(4)).representative()
-            )
-        );
+        // These keys should be the same evaluation * rv and rw, which are two
+        assert_eq!(eval_key.gv_ks[0], g.operate_with_scalar(&(v * FE::new(2))));
+        assert_eq!(eval_key.gw_ks[0], g.operate_with_scalar(&(w * FE::new(2))));
+        // ry = rv * rw is four
+        assert_eq!(eval_key.gy_ks[0], g.operate_with_scalar(&(y * FE::new(4))));
 
-        // alpha * rv and alpha * rw is 4
+        // rv * alpha_v and rw * alpha_w are 4
         assert_eq!(
             eval_key.gv_alphaks[0],
-            g.operate_with_self(
-                (test_circuit.v_mid()[0].evaluate(r5) * FE::new(4)).representative()
-            )
+            g.operate_with_scalar(&(v * FE::new(4)))
         );
         assert_eq!(
-            eval_key.gv_alphaks[0],
-            g.operate_with_self(
-                (test_circuit.v_mid()[0].evaluate(r5) * FE::new(4)).representative()
-            )
+            eval_key.gw_alphaks[0],
+            g.operate_with_scalar(&(w * FE::new(4)))
         );
-        // alpha * ry and alpha * rw is 8
+        // ry * alpha_y is 8
         assert_eq!(
-            eval_key.gv_alphaks[0],
-            g.operate_with_self(
-                (test_circuit.v_mid()[0].evaluate(r5) * FE::new(8)).representative()
-            )
+            eval_key.gy_alphaks[0],
+            g.operate_with_scalar(&(y * FE::new(8)))
         );
 
         assert_eq!(
             eval_key.g_beta[0],
-            // beta * rv is 4
-            g.operate_with_self(
-                (test_circuit.v_mid()[0].evaluate(r5) * FE::new(4) +
-            test_circuit.w_mid()[0].evaluate(r5) * FE::new(4) +
-            // beta * ry is 8
-            test_circuit.y_mid()[0].evaluate(r5) * FE::new(8))
-                .representative()
-            )
+            // beta * rv and beta * rw are 4, beta * ry is 8
+            g.operate_with_scalar(&(v * FE::new(4) + w * FE::new(4) + y * FE::new(8)))
         )
     }
 
@@ -336,4 +360,129 @@ mod tests {
         assert_eq!(vk.gw_ks.len(), 6);
         assert_eq!(vk.gy_ks.len(), 6);
     }
+
+    type S = Symbolic<FE>;
+
+    /// A QAP with one input, one mid and one output wire, where no polynomial
+    /// is zero, so that every key entry depends on the toxic waste.
+    fn symbolic_qap() -> QAP<FE> {
+        let polynomials = |first: u64| -> Vec<Polynomial<FE>> {
+            (first..first + 4)
+                .map(|k| Polynomial::new(vec![FE::from_u64(k), FE::one()]))
+                .collect()
+        };
+        let target = Polynomial::new(vec![FE::one(), FE::zero(), FE::one()]);
+        QAP::new(polynomials(1), polynomials(2), polynomials(3), target, 1, 1).unwrap()
+    }
+
+    fn symbolic_keys() -> (EvaluationKey<S>, VerificationKey<S>) {
+        symbolic::setup(&symbolic_qap())
+    }
+
+    fn power(x: &S, exponent: usize) -> S {
+        (0..exponent).fold(S::generator(), |power, _| power * x.clone())
+    }
+
+    /// Returns the sum of c_i * s^i over the coefficients of `p`.
+    fn at_s(p: &Polynomial<FE>) -> S {
+        let s = S::variable(Variable::S);
+        p.coefficients()
+            .iter()
+            .enumerate()
+            .fold(S::zero(), |sum, (i, c)| sum + S::from(*c) * power(&s, i))
+    }
+
+    #[test]
+    fn symbolic_evaluation_key_entries_are_the_expected_expressions() {
+        let qap = symbolic_qap();
+        let (ek, _) = symbolic_keys();
+        let var = S::variable;
+        let s = var(Variable::S);
+        let (rv, rw) = (var(Variable::Rv), var(Variable::Rw));
+        let ry = rv.clone() * rw.clone();
+        let beta = var(Variable::Beta);
+
+        for k in 0..qap.v_mid().len() {
+            let v = at_s(&qap.v_mid()[k]);
+            let w = at_s(&qap.w_mid()[k]);
+            let y = at_s(&qap.y_mid()[k]);
+            assert_eq!(ek.gv_ks[k], rv.clone() * v.clone());
+            assert_eq!(ek.gw_ks[k], rw.clone() * w.clone());
+            assert_eq!(ek.gy_ks[k], ry.clone() * y.clone());
+            assert_eq!(
+                ek.gv_alphaks[k],
+                rv.clone() * var(Variable::AlphaV) * v.clone()
+            );
+            assert_eq!(
+                ek.gw_alphaks[k],
+                rw.clone() * var(Variable::AlphaW) * w.clone()
+            );
+            assert_eq!(
+                ek.gy_alphaks[k],
+                ry.clone() * var(Variable::AlphaY) * y.clone()
+            );
+            assert_eq!(
+                ek.g_beta[k],
+                beta.clone() * (rv.clone() * v + rw.clone() * w + ry.clone() * y)
+            );
+        }
+        for (i, g_s_i) in ek.g_s_i.iter().enumerate() {
+            assert_eq!(*g_s_i, power(&s, i));
+        }
+    }
+
+    #[test]
+    fn symbolic_verification_key_entries_are_the_expected_expressions() {
+        let qap = symbolic_qap();
+        let (_, vk) = symbolic_keys();
+        let var = S::variable;
+        let (rv, rw) = (var(Variable::Rv), var(Variable::Rw));
+        let ry = rv.clone() * rw.clone();
+        let beta_gamma = var(Variable::Beta) * var(Variable::Gamma);
+
+        assert_eq!(vk.g_1, S::generator());
+        assert_eq!(vk.g_2, S::generator());
+        assert_eq!(vk.g_alpha_v, var(Variable::AlphaV));
+        assert_eq!(vk.g_alpha_w, var(Variable::AlphaW));
+        assert_eq!(vk.g_alpha_y, var(Variable::AlphaY));
+        assert_eq!(vk.g_gamma, var(Variable::Gamma));
+        assert_eq!(vk.g1_beta_gamma, beta_gamma);
+        assert_eq!(vk.g2_beta_gamma, beta_gamma);
+        assert_eq!(vk.gy_target_on_s, ry.clone() * at_s(&qap.target));
+        let vs = io_polynomials(qap.v0(), qap.v_input(), qap.v_output());
+        let ws = io_polynomials(qap.w0(), qap.w_input(), qap.w_output());
+        let ys = io_polynomials(qap.y0(), qap.y_input(), qap.y_output());
+        for k in 0..vs.len() {
+            assert_eq!(vk.gv_ks[k], rv.clone() * at_s(vs[k]));
+            assert_eq!(vk.gw_ks[k], rw.clone() * at_s(ws[k]));
+            assert_eq!(vk.gy_ks[k], ry.clone() * at_s(ys[k]));
+        }
+    }
+
+    // The verifier checks these relations through pairings, which are
+    // products in the symbolic backend.
+    #[test]
+    fn symbolic_keys_satisfy_the_relations_checked_by_the_verifier() {
+        let (ek, vk) = symbolic_keys();
+        let e = S::pairing;
+        for k in 0..ek.gv_ks.len() {
+            assert_eq!(
+                e(&ek.gv_alphaks[k], &vk.g_2),
+                e(&ek.gv_ks[k], &vk.g_alpha_v)
+            );
+            assert_eq!(
+                e(&vk.g_1, &ek.gw_alphaks[k]),
+                e(&vk.g_alpha_w, &ek.gw_ks[k])
+            );
+            assert_eq!(
+                e(&ek.gy_alphaks[k], &vk.g_2),
+                e(&ek.gy_ks[k], &vk.g_alpha_y)
+            );
+            assert_eq!(
+                e(&ek.g_beta[k], &vk.g_gamma),
+                e(&ek.gv_ks[k].operate_with(&ek.gy_ks[k]), &vk.g2_beta_gamma)
+                    .operate_with(&e(&vk.g1_beta_gamma, &ek.gw_ks[k]))
+            );
+        }
+    }
 }
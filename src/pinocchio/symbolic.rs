@@ -0,0 +1,349 @@
+use super::setup::{generate_keys, EvaluationKey, ToxicWaste, VerificationKey};
+use crate::circuits::qap::QuadraticArithmeticProgram as QAP;
+use crate::math::cyclic_group::{CyclicGroup, Group, PairingEngine};
+use crate::math::field::PrimeField;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops;
+
+/// A variable of the toxic waste of Pinocchio's setup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variable {
+    S,
+    AlphaV,
+    AlphaW,
+    AlphaY,
+    Beta,
+    Rv,
+    Rw,
+    Gamma,
+}
+
+impl Variable {
+    const ALL: [Variable; 8] = [
+        Variable::S,
+        Variable::AlphaV,
+        Variable::AlphaW,
+        Variable::AlphaY,
+        Variable::Beta,
+        Variable::Rv,
+        Variable::Rw,
+        Variable::Gamma,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Variable::S => "s",
+            Variable::AlphaV => "alpha_v",
+            Variable::AlphaW => "alpha_w",
+            Variable::AlphaY => "alpha_y",
+            Variable::Beta => "beta",
+            Variable::Rv => "rv",
+            Variable::Rw => "rw",
+            Variable::Gamma => "gamma",
+        }
+    }
+}
+
+/// Exponents of a monomial, indexed by the position of each variable in `Variable::ALL`.
+type Monomial = [u32; Variable::ALL.len()];
+
+/// A formal polynomial in the variables of the toxic waste with coefficients in `F`.
+///
+/// It is a group backend for debugging the setup, like `FieldElement` but symbolic:
+/// the hiding of a value is the value itself, and `setup` builds every key entry
+/// as an exact expression of the toxic waste. The pairing is the product, with
+/// the target group under addition, so the checks of the verifier can be traced too.
+///
+/// It is not a field, since only constants can be inverted, so the key generation
+/// takes it through `ToxicWasteValue` rather than as the scalar of the engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbolic<F: PrimeField> {
+    /// Non-zero coefficients of the polynomial, by monomial.
+    terms: BTreeMap<Monomial, F>,
+}
+
+impl<F: PrimeField> Symbolic<F> {
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Self {
+            terms: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the constant polynomial `value`.
+    pub fn constant(value: F) -> Self {
+        Self::from_terms([([0; Variable::ALL.len()], value)])
+    }
+
+    /// Returns the polynomial made of the variable alone.
+    pub fn variable(variable: Variable) -> Self {
+        let mut monomial = [0; Variable::ALL.len()];
+        let position = Variable::ALL.iter().position(|v| *v == variable);
+        monomial[position.expect("Every variable is in `Variable::ALL`.")] = 1;
+        Self::from_terms([(monomial, F::one())])
+    }
+
+    /// Returns the constant term if the polynomial is constant, or `None` otherwise.
+    pub fn as_constant(&self) -> Option<F> {
+        match self.terms.iter().next() {
+            None => Some(F::zero()),
+            Some((monomial, coefficient))
+                if self.terms.len() == 1 && monomial.iter().all(|e| *e == 0) =>
+            {
+                Some(*coefficient)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Adds up the terms, dropping the monomials whose coefficients cancel out.
+    fn from_terms(terms: impl IntoIterator<Item = (Monomial, F)>) -> Self {
+        let mut sum = BTreeMap::new();
+        for (monomial, coefficient) in terms {
+            let entry = sum.entry(monomial).or_insert_with(F::zero);
+            *entry = *entry + coefficient;
+        }
+        sum.retain(|_, coefficient| *coefficient != F::zero());
+        Self { terms: sum }
+    }
+}
+
+impl<F: PrimeField> ops::Add for Symbolic<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_terms(self.terms.into_iter().chain(other.terms))
+    }
+}
+
+impl<F: PrimeField> ops::Neg for Symbolic<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_terms(self.terms.into_iter().map(|(m, c)| (m, -c)))
+    }
+}
+
+impl<F: PrimeField> ops::Sub for Symbolic<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<F: PrimeField> ops::Mul for Symbolic<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_terms(self.terms.iter().flat_map(|(m1, c1)| {
+            other.terms.iter().map(move |(m2, c2)| {
+                let mut monomial = *m1;
+                for (exponent, other_exponent) in monomial.iter_mut().zip(m2) {
+                    *exponent += other_exponent;
+                }
+                (monomial, *c1 * *c2)
+            })
+        }))
+    }
+}
+
+impl<F: PrimeField> From<F> for Symbolic<F> {
+    fn from(value: F) -> Self {
+        Self::constant(value)
+    }
+}
+
+/// Writes the polynomial as a sum of terms like `2*s^2*rv`, with the coefficients
+/// closer to `-1` than to `1` written as subtractions.
+impl<F: PrimeField> fmt::Display for Symbolic<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (monomial, coefficient)) in self.terms.iter().enumerate() {
+            let is_negative = (-*coefficient).representative() < coefficient.representative();
+            let magnitude = if is_negative {
+                -*coefficient
+            } else {
+                *coefficient
+            };
+            match (i, is_negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            let mut factors: Vec<String> = Variable::ALL
+                .iter()
+                .zip(monomial)
+                .filter(|(_, exponent)| **exponent > 0)
+                .map(|(variable, exponent)| match exponent {
+                    1 => variable.name().to_string(),
+                    _ => format!("{}^{exponent}", variable.name()),
+                })
+                .collect();
+            if magnitude != F::one() || factors.is_empty() {
+                factors.insert(0, format!("{:?}", magnitude.representative()));
+            }
+            write!(f, "{}", factors.join("*"))?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> Group for Symbolic<F> {
+    fn neutral_element() -> Self {
+        Self::zero()
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        self.clone() + other.clone()
+    }
+
+    fn inverse(&self) -> Self {
+        -self.clone()
+    }
+}
+
+impl<F: PrimeField> CyclicGroup for Symbolic<F> {
+    type Scalar = F;
+
+    fn generator() -> Self {
+        Self::constant(F::one())
+    }
+
+    fn operate_with_scalar(&self, scalar: &F) -> Self {
+        self.clone() * Self::constant(*scalar)
+    }
+}
+
+/// Symmetric pairing over the polynomials, the target group is the ring under addition.
+impl<F: PrimeField> PairingEngine for Symbolic<F> {
+    type Scalar = F;
+    type G1 = Self;
+    type G2 = Self;
+    type GT = Self;
+    type G1Prepared = Self;
+    type G2Prepared = Self;
+
+    fn pairing(p: &Self, q: &Self) -> Self {
+        p.clone() * q.clone()
+    }
+
+    fn prepare_g1(p: &Self) -> Self {
+        p.clone()
+    }
+
+    fn prepare_g2(q: &Self) -> Self {
+        q.clone()
+    }
+
+    fn multi_pairing_prepared(pairs: &[(&Self, &Self)]) -> Self {
+        pairs
+            .iter()
+            .fold(Self::neutral_element(), |product, (p, q)| {
+                product.operate_with(&Self::pairing(p, q))
+            })
+    }
+}
+
+/// Runs the setup of `qap` with `ToxicWaste::symbolic()`, which gives
+/// every key entry as the expression of the toxic waste that it hides.
+pub fn setup<F: PrimeField>(
+    qap: &QAP<F>,
+) -> (EvaluationKey<Symbolic<F>>, VerificationKey<Symbolic<F>>) {
+    generate_keys(qap, &ToxicWaste::symbolic(), Clone::clone, Clone::clone)
+}
+
+impl<F: PrimeField> ToxicWaste<Symbolic<F>> {
+    /// Returns the toxic waste whose every element is its own variable.
+    pub fn symbolic() -> Self {
+        let v = Symbolic::variable;
+        ToxicWaste::new(
+            v(Variable::S),
+            v(Variable::AlphaV),
+            v(Variable::AlphaW),
+            v(Variable::AlphaY),
+            v(Variable::Beta),
+            v(Variable::Rv),
+            v(Variable::Rw),
+            v(Variable::Gamma),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::field_element::FieldElement;
+
+    // Big enough for the coefficients of the tests not to wrap around.
+    type FE = FieldElement<59>;
+    type S = Symbolic<FE>;
+
+    fn s() -> S {
+        S::variable(Variable::S)
+    }
+
+    fn rv() -> S {
+        S::variable(Variable::Rv)
+    }
+
+    fn constant(value: u128) -> S {
+        S::constant(FE::new(value))
+    }
+
+    #[test]
+    fn operations_follow_the_ring_axioms() {
+        let a = s() * rv() + constant(2);
+        let b = rv() - S::variable(Variable::Beta);
+        assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+        assert_eq!(
+            (a.clone() + b.clone()) * s(),
+            a.clone() * s() + b.clone() * s()
+        );
+        assert_eq!(a.clone() - a.clone(), S::zero());
+        assert_eq!(a.clone() * constant(1), a);
+    }
+
+    #[test]
+    fn distinct_monomials_do_not_mix() {
+        assert_ne!(s() * rv(), s() + rv());
+        assert_ne!(s() * s(), s() * constant(2));
+        assert_ne!(rv(), S::variable(Variable::Rw));
+    }
+
+    #[test]
+    fn as_constant_returns_the_constant_term_of_constants() {
+        assert_eq!(constant(3).as_constant(), Some(FE::new(3)));
+        assert_eq!(S::zero().as_constant(), Some(FE::new(0)));
+        assert_eq!((s() + constant(1)).as_constant(), None);
+    }
+
+    #[test]
+    fn display_writes_the_terms_of_the_polynomial() {
+        assert_eq!(S::zero().to_string(), "0");
+        assert_eq!(constant(3).to_string(), "3");
+        let a = constant(2) * s() * s() * rv() - S::variable(Variable::AlphaV) - constant(1);
+        assert_eq!(a.to_string(), "-1 - alpha_v + 2*s^2*rv");
+    }
+
+    #[test]
+    fn scalars_multiply_as_constants() {
+        assert_eq!(s().operate_with_scalar(&FE::new(3)), constant(3) * s());
+        assert!(s().is_in_subgroup());
+    }
+
+    #[test]
+    fn pairing_is_the_product() {
+        let p = s() + constant(1);
+        let q = rv();
+        assert_eq!(S::pairing(&p, &q), p.clone() * q.clone());
+        assert_eq!(
+            S::multi_pairing(&[(p.clone(), q.clone()), (q.clone(), p.clone())]),
+            constant(2) * p * q
+        );
+    }
+}
@@ -1,30 +1,102 @@
-use super::super::config::ORDER_R;
 use super::prover::Proof;
 use super::setup::VerificationKey;
-use crate::math::{self, cyclic_group::CyclicBilinearGroup};
-use math::field_element::FieldElement;
+use crate::math::{
+    self,
+    cyclic_group::{CyclicGroup, Group, PairingEngine},
+};
 use math::msm::msm;
 
-type FE = FieldElement<ORDER_R>;
+/// Verifying key with the fixed arguments of the verifier pairings prepared
+/// once, so that services verifying many proofs against the same key do not
+/// compute their Miller loop lines again for every proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedVerificationKey<E: PairingEngine> {
+    verification_key: VerificationKey<E>,
+    g_1: E::G1Prepared,
+    g_2: E::G2Prepared,
+    g_alpha_v: E::G2Prepared,
+    g_alpha_w_inverse: E::G1Prepared,
+    g_alpha_y: E::G2Prepared,
+    g_gamma: E::G2Prepared,
+    g1_beta_gamma_inverse: E::G1Prepared,
+    g2_beta_gamma: E::G2Prepared,
+    gy_target_on_s: E::G2Prepared,
+}
+
+impl<E: PairingEngine> PreparedVerificationKey<E> {
+    pub fn new(verification_key: &VerificationKey<E>) -> Self {
+        let vk = verification_key;
+        Self {
+            verification_key: vk.clone(),
+            g_1: E::prepare_g1(&vk.g_1),
+            g_2: E::prepare_g2(&vk.g_2),
+            g_alpha_v: E::prepare_g2(&vk.g_alpha_v),
+            g_alpha_w_inverse: E::prepare_g1(&vk.g_alpha_w.inverse()),
+            g_alpha_y: E::prepare_g2(&vk.g_alpha_y),
+            g_gamma: E::prepare_g2(&vk.g_gamma),
+            g1_beta_gamma_inverse: E::prepare_g1(&vk.g1_beta_gamma.inverse()),
+            g2_beta_gamma: E::prepare_g2(&vk.g2_beta_gamma),
+            gy_target_on_s: E::prepare_g2(&vk.gy_target_on_s),
+        }
+    }
+}
 
 /// Pinocchio's verification algorithm.
-pub fn verify<T: CyclicBilinearGroup>(
-    verification_key: &VerificationKey<T>,
-    proof: &Proof<T>,
-    c_input_output: &[FE],
+pub fn verify<E: PairingEngine>(
+    verification_key: &VerificationKey<E>,
+    proof: &Proof<E>,
+    c_input_output: &[E::Scalar],
+) -> bool {
+    verify_prepared(
+        &PreparedVerificationKey::new(verification_key),
+        proof,
+        c_input_output,
+    )
+}
+
+/// Pinocchio's verification algorithm with a prepared verifying key.
+pub fn verify_prepared<E: PairingEngine>(
+    verification_key: &PreparedVerificationKey<E>,
+    proof: &Proof<E>,
+    c_input_output: &[E::Scalar],
 ) -> bool {
+    if !check_proof_elements_in_subgroups(proof) {
+        return false;
+    }
     let b1 = check_divisibility(verification_key, proof, c_input_output);
     let b2 = check_appropiate_spans(verification_key, proof);
     let b3 = check_same_linear_combinations(verification_key, proof);
     b1 && b2 && b3
 }
 
-pub fn check_divisibility<T: CyclicBilinearGroup>(
-    verification_key: &VerificationKey<T>,
-    proof: &Proof<T>,
-    input_output: &[FE],
+/// Checks that every element of the proof lies in the subgroup of
+/// order r, so that no small-order component can slip through the pairings.
+pub fn check_proof_elements_in_subgroups<E: PairingEngine>(proof: &Proof<E>) -> bool {
+    let g1_elements = [
+        &proof.g_vs,
+        &proof.g_ys,
+        &proof.g_hs,
+        &proof.g_alpha_vs,
+        &proof.g_alpha_ys,
+        &proof.g_beta_vwy,
+    ];
+    let g2_elements = [&proof.g_ws, &proof.g_alpha_ws];
+    g1_elements.iter().all(|g| g.is_in_subgroup()) && g2_elements.iter().all(|g| g.is_in_subgroup())
+}
+
+/// Checks that e(V, W) = e(H, T(s)) * e(Y, g_2), where V, W and Y are the hidings
+/// of the full linear combinations, including the input and output values.
+/// Returns `false` when the number of input and output values does not match the key.
+pub fn check_divisibility<E: PairingEngine>(
+    verification_key: &PreparedVerificationKey<E>,
+    proof: &Proof<E>,
+    input_output: &[E::Scalar],
 ) -> bool {
-    let vk = verification_key;
+    let pvk = verification_key;
+    let vk = &pvk.verification_key;
+    if input_output.len() != vk.gv_ks.len() - 1 {
+        return false;
+    }
 
     let hiding_v = vk.gv_ks[0]
         .operate_with(&msm(input_output, &vk.gv_ks[1..]))
@@ -36,57 +108,91 @@ pub fn check_divisibility<T: CyclicBilinearGroup>(
         .operate_with(&msm(input_output, &vk.gy_ks[1..]))
         .operate_with(&proof.g_ys);
 
-    let lhs = hiding_v.pairing(&hiding_w);
-    let rhs_1 = verification_key.gy_target_on_s.pairing(&proof.g_hs);
-    let rhs_2 = hiding_y.pairing(&vk.g_1);
-
-    lhs == rhs_1 * rhs_2
+    // e(V, W) * e(H, T(s))^-1 * e(Y, g_2)^-1 = 1
+    pairing_product_is_one::<E>(&[
+        (&E::prepare_g1(&hiding_v), &E::prepare_g2(&hiding_w)),
+        (&E::prepare_g1(&proof.g_hs.inverse()), &pvk.gy_target_on_s),
+        (&E::prepare_g1(&hiding_y.inverse()), &pvk.g_2),
+    ])
 }
 
-pub fn check_appropiate_spans<T: CyclicBilinearGroup>(
-    verification_key: &VerificationKey<T>,
-    proof: &Proof<T>,
+/// Checks that the hidings of v, w and y are in the spans of their polynomials,
+/// through e(alpha_v * V, g_2) = e(V, alpha_v * g_2) and the same for w and y.
+pub fn check_appropiate_spans<E: PairingEngine>(
+    verification_key: &PreparedVerificationKey<E>,
+    proof: &Proof<E>,
 ) -> bool {
-    let vk = verification_key;
+    let pvk = verification_key;
 
-    let b1 = proof.g_alpha_vs.pairing(&vk.g_1) == proof.g_vs.pairing(&vk.g_alpha_v);
-    let b2 = proof.g_alpha_ws.pairing(&vk.g_1) == proof.g_ws.pairing(&vk.g_alpha_w);
-    let b3 = proof.g_alpha_ys.pairing(&vk.g_1) == proof.g_ys.pairing(&vk.g_alpha_y);
+    let b1 = pairing_product_is_one::<E>(&[
+        (&E::prepare_g1(&proof.g_alpha_vs), &pvk.g_2),
+        (&E::prepare_g1(&proof.g_vs.inverse()), &pvk.g_alpha_v),
+    ]);
+    let b2 = pairing_product_is_one::<E>(&[
+        (&pvk.g_1, &E::prepare_g2(&proof.g_alpha_ws)),
+        (&pvk.g_alpha_w_inverse, &E::prepare_g2(&proof.g_ws)),
+    ]);
+    let b3 = pairing_product_is_one::<E>(&[
+        (&E::prepare_g1(&proof.g_alpha_ys), &pvk.g_2),
+        (&E::prepare_g1(&proof.g_ys.inverse()), &pvk.g_alpha_y),
+    ]);
     b1 && b2 && b3
 }
 
-pub fn check_same_linear_combinations<T: CyclicBilinearGroup>(
-    verification_key: &VerificationKey<T>,
-    proof: &Proof<T>,
+/// Checks that the same coefficients were used for v, w and y, through
+/// e(beta * (V + W + Y), gamma) = e(V + Y, beta * gamma * g_2) * e(beta * gamma * g_1, W).
+pub fn check_same_linear_combinations<E: PairingEngine>(
+    verification_key: &PreparedVerificationKey<E>,
+    proof: &Proof<E>,
 ) -> bool {
-    let vk = verification_key;
-
-    proof.g_beta_vwy.pairing(&vk.g_gamma)
-        == (proof
-            .g_vs
-            .operate_with(&proof.g_ws)
-            .operate_with(&proof.g_ys))
-        .pairing(&vk.g_beta_gamma)
+    let pvk = verification_key;
+
+    // Since the hiding of w lives in G2, its share of the
+    // linear combination is paired separately.
+    pairing_product_is_one::<E>(&[
+        (&E::prepare_g1(&proof.g_beta_vwy), &pvk.g_gamma),
+        (
+            &E::prepare_g1(&proof.g_vs.operate_with(&proof.g_ys).inverse()),
+            &pvk.g2_beta_gamma,
+        ),
+        (&pvk.g1_beta_gamma_inverse, &E::prepare_g2(&proof.g_ws)),
+    ])
+}
+
+/// Returns `true` if the product of the pairings of every pair is one,
+/// sharing the final exponentiation among them.
+fn pairing_product_is_one<E: PairingEngine>(pairs: &[(&E::G1Prepared, &E::G2Prepared)]) -> bool {
+    E::multi_pairing_prepared(pairs) == E::GT::neutral_element()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::elliptic_curve::EllipticCurveElement;
+    use crate::config::{DefaultCurve, ORDER_R};
+    use crate::math::elliptic_curve;
+    use crate::math::field::Field;
+    use crate::math::field_element::FieldElement;
+    use crate::math::field_extension_element::FieldExtensionElement;
+
+    type FE = FieldElement<ORDER_R>;
+    type EllipticCurveElement = elliptic_curve::EllipticCurveElement<DefaultCurve>;
 
     // In this tests we do not hide elements. We work with the raw values instead.
     // These are easier to handle and computations can be done with pen and paper.
     // Check out the prover tests to see where most of these values come from.
-    fn dummy_verification_data_without_hidings() -> (VerificationKey<FE>, Proof<FE>, Vec<FE>) {
+    fn dummy_verification_data_without_hidings() -> (PreparedVerificationKey<FE>, Proof<FE>, Vec<FE>)
+    {
         // Dummy verification key assuming
         // (s, r_v, r_w, alpha_v, alpha_w, alpha_y, beta, gamma) = (1, 1, 1, 2, 2, 2, 3, 1)
         let verification_key = VerificationKey {
             g_1: FE::new(1),
+            g_2: FE::new(1),
             g_alpha_v: FE::new(2),
             g_alpha_w: FE::new(2),
             g_alpha_y: FE::new(2),
             g_gamma: FE::new(3),
-            g_beta_gamma: FE::new(3),
+            g1_beta_gamma: FE::new(3),
+            g2_beta_gamma: FE::new(3),
             gy_target_on_s: FE::new(2),
             gv_ks: vec![FE::new(0), FE::new(6), FE::new(6)],
             gw_ks: vec![FE::new(0), FE::new(6), FE::new(6)],
@@ -113,7 +219,11 @@ mod tests {
             g_beta_vwy: FE::new(18),
         };
 
-        (verification_key, proof, input_output)
+        (
+            PreparedVerificationKey::new(&verification_key),
+            proof,
+            input_output,
+        )
     }
 
     #[test]
@@ -137,7 +247,7 @@ mod tests {
     #[test]
     fn test_divisibility_check_correct_on_incorrect_input_output_without_hidings() {
         let (verification_key, proof, _) = dummy_verification_data_without_hidings();
-        let input_output = vec![FE::new(0), FE::new(0)];
+        let input_output = vec![FE::new(0), FE::new(1)];
         assert!(!check_divisibility(
             &verification_key,
             &proof,
@@ -161,7 +271,7 @@ mod tests {
 
     // The following is the same as before but with hidings on elliptic curves.
     fn dummy_verification_data_with_elliptic_curve_hidings() -> (
-        VerificationKey<EllipticCurveElement>,
+        PreparedVerificationKey<EllipticCurveElement>,
         Proof<EllipticCurveElement>,
         Vec<FE>,
     ) {
@@ -169,27 +279,29 @@ mod tests {
         // Dummy verification key assuming
         // (s, r_v, r_w, alpha_v, alpha_w, alpha_y, beta, gamma) = (1, 1, 1, 2, 2, 2, 3, 1)
         let verification_key = VerificationKey {
-            g_1: g.operate_with_self(1),
-            g_alpha_v: g.operate_with_self(2),
-            g_alpha_w: g.operate_with_self(2),
-            g_alpha_y: g.operate_with_self(2),
-            g_gamma: g.operate_with_self(3),
-            g_beta_gamma: g.operate_with_self(3),
-            gy_target_on_s: g.operate_with_self(2),
+            g_1: g.operate_with_self(1u64),
+            g_2: g.operate_with_self(1u64),
+            g_alpha_v: g.operate_with_self(2u64),
+            g_alpha_w: g.operate_with_self(2u64),
+            g_alpha_y: g.operate_with_self(2u64),
+            g_gamma: g.operate_with_self(3u64),
+            g1_beta_gamma: g.operate_with_self(3u64),
+            g2_beta_gamma: g.operate_with_self(3u64),
+            gy_target_on_s: g.operate_with_self(2u64),
             gv_ks: vec![
-                g.operate_with_self(0),
-                g.operate_with_self(6),
-                g.operate_with_self(6),
+                g.operate_with_self(0u64),
+                g.operate_with_self(6u64),
+                g.operate_with_self(6u64),
             ],
             gw_ks: vec![
-                g.operate_with_self(0),
-                g.operate_with_self(6),
-                g.operate_with_self(6),
+                g.operate_with_self(0u64),
+                g.operate_with_self(6u64),
+                g.operate_with_self(6u64),
             ],
             gy_ks: vec![
-                g.operate_with_self(0),
-                g.operate_with_self(6),
-                g.operate_with_self(6),
+                g.operate_with_self(0u64),
+                g.operate_with_self(6u64),
+                g.operate_with_self(6u64),
             ],
         };
 
@@ -203,17 +315,21 @@ mod tests {
         // This is a valid proof for the above input and output values.
         // See the prover tests to see where this comes from.
         let proof = Proof {
-            g_vs: g.operate_with_self(6),
-            g_ws: g.operate_with_self(6),
-            g_ys: g.operate_with_self(6),
-            g_hs: g.operate_with_self(120),
-            g_alpha_vs: g.operate_with_self(12),
-            g_alpha_ws: g.operate_with_self(12),
-            g_alpha_ys: g.operate_with_self(12),
-            g_beta_vwy: g.operate_with_self(18),
+            g_vs: g.operate_with_self(6u64),
+            g_ws: g.operate_with_self(6u64),
+            g_ys: g.operate_with_self(6u64),
+            g_hs: g.operate_with_self(120u64),
+            g_alpha_vs: g.operate_with_self(12u64),
+            g_alpha_ws: g.operate_with_self(12u64),
+            g_alpha_ys: g.operate_with_self(12u64),
+            g_beta_vwy: g.operate_with_self(18u64),
         };
 
-        (verification_key, proof, input_output)
+        (
+            PreparedVerificationKey::new(&verification_key),
+            proof,
+            input_output,
+        )
     }
 
     #[test]
@@ -246,6 +362,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_verify_rejects_the_wrong_number_of_input_output_values() {
+        let (verification_key, proof, input_output) =
+            dummy_verification_data_with_elliptic_curve_hidings();
+        assert!(!verify_prepared(
+            &verification_key,
+            &proof,
+            &input_output[..1]
+        ));
+        let too_many = [input_output.as_slice(), &[FE::new(0)]].concat();
+        assert!(!verify_prepared(&verification_key, &proof, &too_many));
+    }
+
     #[test]
     fn test_appropiate_spans_correct_on_incorrect_proof_with_elliptic_curve_hidings() {
         let (verification_key, mut proof, _) =
@@ -261,4 +390,37 @@ mod tests {
         proof.g_beta_vwy = EllipticCurveElement::neutral_element();
         assert!(!check_same_linear_combinations(&verification_key, &proof));
     }
+
+    #[test]
+    fn test_proof_elements_in_subgroups_with_elliptic_curve_hidings() {
+        let (_, proof, _) = dummy_verification_data_with_elliptic_curve_hidings();
+        assert!(check_proof_elements_in_subgroups(&proof));
+    }
+
+    #[test]
+    fn test_verify_rejects_proof_elements_outside_the_subgroup() {
+        let (verification_key, mut proof, input_output) =
+            dummy_verification_data_with_elliptic_curve_hidings();
+        assert!(verify_prepared(&verification_key, &proof, &input_output));
+        // (0, 0) lies on the curve but has order 2.
+        let zero = FieldExtensionElement::zero();
+        proof.g_hs = EllipticCurveElement::from_affine(zero.clone(), zero).unwrap();
+        assert!(!check_proof_elements_in_subgroups(&proof));
+        assert!(!verify_prepared(&verification_key, &proof, &input_output));
+    }
+
+    #[test]
+    fn test_verify_prepared_accepts_many_proofs_against_the_same_key() {
+        let (verification_key, proof, input_output) =
+            dummy_verification_data_with_elliptic_curve_hidings();
+        let mut wrong_proof = proof.clone();
+        wrong_proof.g_beta_vwy = EllipticCurveElement::neutral_element();
+        assert!(verify_prepared(&verification_key, &proof, &input_output));
+        assert!(!verify_prepared(
+            &verification_key,
+            &wrong_proof,
+            &input_output
+        ));
+        assert!(verify_prepared(&verification_key, &proof, &input_output));
+    }
 }
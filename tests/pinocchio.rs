@@ -1,24 +1,39 @@
 /// Integration test with the happy path: Setup -> Proof generation -> Proof verification.
 use pinocchio_lambda_vm::circuits::test_utils::{new_test_r1cs, test_qap_solver};
-use pinocchio_lambda_vm::math::elliptic_curve::EllipticCurveElement;
-use pinocchio_lambda_vm::math::field_element::FieldElement as FE;
+use pinocchio_lambda_vm::config::{DefaultCurve, ORDER_R};
+use pinocchio_lambda_vm::math::curves::bls12_381::{self, BLS12381};
+use pinocchio_lambda_vm::math::curves::bn254::{self, BN254};
+use pinocchio_lambda_vm::math::curves::supersingular::{Curve103, Curve43};
+use pinocchio_lambda_vm::math::cyclic_group::PairingEngine;
+use pinocchio_lambda_vm::math::elliptic_curve::{CurveParams, EllipticCurveElement};
+use pinocchio_lambda_vm::math::field::{ConditionalSwap, Field};
+use pinocchio_lambda_vm::math::field_element::FieldElement;
 use pinocchio_lambda_vm::pinocchio::prover;
 use pinocchio_lambda_vm::pinocchio::setup::{setup, EvaluationKey, ToxicWaste, VerificationKey};
-use pinocchio_lambda_vm::pinocchio::verifier;
+use pinocchio_lambda_vm::pinocchio::verifier::{self, PreparedVerificationKey};
 
-fn test_pinocchio(toxic_waste: ToxicWaste) {
+type FE = FieldElement<ORDER_R>;
+
+fn test_pinocchio<E: PairingEngine>(toxic_waste: ToxicWaste<E::Scalar>)
+where
+    E::G1: ConditionalSwap,
+    E::G2: ConditionalSwap,
+{
     // Get example circuit.
     let test_qap = new_test_r1cs().into();
 
     // Construct the evaluation and veryfing key.
-    let (evaluation_key, verification_key): (
-        EvaluationKey<EllipticCurveElement>,
-        VerificationKey<EllipticCurveElement>,
-    ) = setup(&test_qap, &toxic_waste);
+    let (evaluation_key, verification_key): (EvaluationKey<E>, VerificationKey<E>) =
+        setup(&test_qap, &toxic_waste);
 
     // Declare inputs to the circuit. Here we choose the ones from the example
     // of the paper.
-    let inputs = [FE::new(1), FE::new(2), FE::new(3), FE::new(4)];
+    let inputs = [
+        E::Scalar::from_u64(1),
+        E::Scalar::from_u64(2),
+        E::Scalar::from_u64(3),
+        E::Scalar::from_u64(4),
+    ];
 
     // Execute the circuit with the above inputs.
     // Get the output and all the intermediate values,
@@ -46,12 +61,20 @@ fn test_pinocchio(toxic_waste: ToxicWaste) {
 
     // Accept or reject the proof.
     assert!(accepted);
+
+    // A prepared verifying key gives the same answer.
+    let prepared_verification_key = PreparedVerificationKey::new(&verification_key);
+    assert!(verifier::verify_prepared(
+        &prepared_verification_key,
+        &proof,
+        &c_io_vector
+    ));
 }
 
 #[test]
 fn test_pinocchio_random_toxic_wate() {
-    let toxic_waste = ToxicWaste::sample();
-    test_pinocchio(toxic_waste);
+    let toxic_waste = ToxicWaste::<FE>::sample();
+    test_pinocchio::<EllipticCurveElement<DefaultCurve>>(toxic_waste);
 }
 
 #[test]
@@ -66,5 +89,29 @@ fn test_pinocchio_2() {
         FE::new(2),
         FE::new(3),
     );
-    test_pinocchio(toxic_waste);
+    test_pinocchio::<EllipticCurveElement<DefaultCurve>>(toxic_waste);
+}
+
+#[test]
+fn test_pinocchio_over_curve_43() {
+    let toxic_waste = ToxicWaste::<<Curve43 as CurveParams>::ScalarField>::sample();
+    test_pinocchio::<EllipticCurveElement<Curve43>>(toxic_waste);
+}
+
+#[test]
+fn test_pinocchio_over_curve_103() {
+    let toxic_waste = ToxicWaste::<<Curve103 as CurveParams>::ScalarField>::sample();
+    test_pinocchio::<EllipticCurveElement<Curve103>>(toxic_waste);
+}
+
+#[test]
+fn test_pinocchio_over_bn254() {
+    let toxic_waste = ToxicWaste::<bn254::field_extension::Fr>::sample();
+    test_pinocchio::<BN254>(toxic_waste);
+}
+
+#[test]
+fn test_pinocchio_over_bls12_381() {
+    let toxic_waste = ToxicWaste::<bls12_381::field_extension::Fr>::sample();
+    test_pinocchio::<BLS12381>(toxic_waste);
 }